@@ -0,0 +1,175 @@
+use crate::divisors::gcd_extended;
+use crate::modular::mod_mul;
+
+// Sums go via u128 too, as two values just under a large m can pass u64::MAX
+fn mod_add(a: u64, b: u64, m: u64) -> u64 {
+    ((a as u128 + b as u128) % m as u128) as u64
+}
+
+// The map x -> a * x + b (mod m). Any sequence of these composes into another
+// one, so a shuffle or a linear congruential generator can be repeated an
+// enormous number of times by squaring.
+#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq)]
+pub struct AffineMod {
+    pub a: u64,
+    pub b: u64,
+    pub m: u64,
+}
+
+impl AffineMod {
+    // a and b may be negative, they are reduced into 0..m
+    pub fn new(a: i128, b: i128, m: u64) -> AffineMod {
+        assert!(m > 0, "modulus must be positive");
+        AffineMod {
+            a: a.rem_euclid(m as i128) as u64,
+            b: b.rem_euclid(m as i128) as u64,
+            m,
+        }
+    }
+
+    pub fn identity(m: u64) -> AffineMod {
+        AffineMod::new(1, 0, m)
+    }
+
+    pub fn apply(&self, x: u64) -> u64 {
+        mod_add(mod_mul(self.a, x % self.m, self.m), self.b, self.m)
+    }
+
+    // self first, then other: x -> other(self(x))
+    pub fn then(&self, other: &AffineMod) -> AffineMod {
+        assert_eq!(self.m, other.m, "moduli must match");
+        let m = self.m;
+        AffineMod {
+            a: mod_mul(other.a, self.a, m),
+            b: mod_add(mod_mul(other.a, self.b, m), other.b, m),
+            m,
+        }
+    }
+
+    // y = a * x + b  =>  x = a^-1 * y - a^-1 * b. None unless a and m are coprime.
+    pub fn inverse(&self) -> Option<AffineMod> {
+        let (g, inv, _) = gcd_extended(self.a as i128, self.m as i128);
+        if g != 1 {
+            return None;
+        }
+        let m = self.m;
+        let inv = inv.rem_euclid(m as i128) as u64;
+        // -inv * b, reduced as u64 since the product can pass i128::MAX
+        Some(AffineMod {
+            a: inv,
+            b: mod_mul(inv, (m - self.b) % m, m),
+            m,
+        })
+    }
+
+    // The map applied n times, by squaring
+    pub fn pow(&self, n: u64) -> AffineMod {
+        let mut result = AffineMod::identity(self.m);
+        let mut square = *self;
+        let mut n = n;
+        while n > 0 {
+            if n & 1 == 1 {
+                result = result.then(&square);
+            }
+            square = square.then(&square);
+            n >>= 1;
+        }
+        result
+    }
+
+    // Shuffle techniques on a deck of m cards, as maps from a card's position
+    // before the shuffle to its position after
+
+    pub fn deal_into_new_stack(m: u64) -> AffineMod {
+        AffineMod::new(-1, -1, m)
+    }
+
+    pub fn cut(n: i64, m: u64) -> AffineMod {
+        AffineMod::new(1, -(n as i128), m)
+    }
+
+    pub fn deal_with_increment(n: u64, m: u64) -> AffineMod {
+        AffineMod::new(n as i128, 0, m)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Card at each position after shuffling a deck in factory order
+    fn deck(shuffle: &AffineMod) -> Vec<u64> {
+        let mut deck = vec![0; shuffle.m as usize];
+        for card in 0..shuffle.m {
+            deck[shuffle.apply(card) as usize] = card;
+        }
+        deck
+    }
+
+    #[test]
+    fn test_shuffles() {
+        let m = 10;
+        let shuffle = AffineMod::deal_with_increment(7, m)
+            .then(&AffineMod::deal_into_new_stack(m))
+            .then(&AffineMod::deal_into_new_stack(m));
+        assert_eq!(deck(&shuffle), vec![0, 3, 6, 9, 2, 5, 8, 1, 4, 7]);
+
+        let shuffle = AffineMod::cut(6, m)
+            .then(&AffineMod::deal_with_increment(7, m))
+            .then(&AffineMod::deal_into_new_stack(m));
+        assert_eq!(deck(&shuffle), vec![3, 0, 7, 4, 1, 8, 5, 2, 9, 6]);
+
+        assert_eq!(
+            deck(&AffineMod::cut(-4, m)),
+            vec![6, 7, 8, 9, 0, 1, 2, 3, 4, 5]
+        );
+    }
+
+    #[test]
+    fn test_inverse() {
+        let m = 119315717514047;
+        let f = AffineMod::new(-12345678901, 98765432109, m);
+        let inv = f.inverse().unwrap();
+        assert_eq!(f.then(&inv), AffineMod::identity(m));
+        assert_eq!(inv.apply(f.apply(2020)), 2020);
+        assert_eq!(AffineMod::new(2, 1, 10).inverse(), None);
+    }
+
+    #[test]
+    fn test_pow() {
+        let f = AffineMod::new(3, 7, 1009);
+        let repeated = (0..100).fold(AffineMod::identity(1009), |acc, _| acc.then(&f));
+        assert_eq!(f.pow(100), repeated);
+        assert_eq!(f.pow(0), AffineMod::identity(1009));
+
+        // 10^14 shuffles of a huge deck, undone by the inverse
+        let m = 119315717514047;
+        let f = AffineMod::new(-12345678901, 98765432109, m);
+        let n = 101741582076661;
+        let g = f.pow(n);
+        assert_eq!(g.inverse().unwrap().apply(g.apply(2020)), 2020);
+        assert_eq!(g.then(&f), f.pow(n + 1));
+    }
+
+    #[test]
+    fn test_lcg() {
+        // Generator from the duelling generators puzzle
+        let gen = AffineMod::new(16807, 0, 2147483647);
+        assert_eq!(gen.apply(65), 1092455);
+        assert_eq!(gen.pow(5).apply(65), 1352636452);
+    }
+
+    #[test]
+    fn test_large_modulus() {
+        // Near u64::MAX the sum a * x + b passes u64::MAX before it's reduced
+        let m = u64::MAX - 58;
+        let f = AffineMod::new(1, -1, m);
+        assert_eq!(f.apply(m - 1), m - 2);
+        assert_eq!(f.then(&f).apply(0), m - 2);
+        assert_eq!(f.pow(3).apply(1), m - 2);
+        let g = AffineMod::new(11, -1, m);
+        let inv = g.inverse().unwrap();
+        assert_eq!(g.then(&inv), AffineMod::identity(m));
+        assert_eq!(inv.apply(g.apply(m - 1)), m - 1);
+    }
+}
@@ -0,0 +1,278 @@
+use num::integer::{div_floor, Roots};
+use num::{BigInt, BigRational, One, Signed, Zero};
+
+// Continued fraction of p/q, eg. 415/93 = [4; 2, 6, 7]. Terms after the first
+// are positive, the first takes the sign of p/q.
+pub fn continued_fraction(p: i128, q: i128) -> Vec<i128> {
+    assert!(q != 0, "denominator must be non-zero");
+    let mut terms = Vec::new();
+    let (mut p, mut q) = (p, q);
+    while q != 0 {
+        let a = div_floor(p, q);
+        terms.push(a);
+        (p, q) = (q, p - a * q);
+    }
+    terms
+}
+
+// Continued fraction of sqrt(n) as the whole part and the repeating period,
+// eg. sqrt(23) = [4; (1, 3, 1, 8)]. The period is empty for a perfect square.
+pub fn sqrt_continued_fraction(n: u64) -> (u64, Vec<u64>) {
+    let a0 = n.sqrt();
+    let mut period = Vec::new();
+    if a0 * a0 == n {
+        return (a0, period);
+    }
+    // sqrt(n) = a0 + ..., each complete quotient is (sqrt(n) + m) / d
+    let (mut m, mut d, mut a) = (0, 1, a0);
+    while a != 2 * a0 {
+        m = d * a - m;
+        d = (n - m * m) / d;
+        a = (a0 + m) / d;
+        period.push(a);
+    }
+    (a0, period)
+}
+
+// Successive convergents h/k of a continued fraction, from the recurrence
+//      h_n = a_n * h_(n-1) + h_(n-2)
+#[derive(Debug)]
+pub struct Convergents<I> {
+    terms: I,
+    h: (BigInt, BigInt),
+    k: (BigInt, BigInt),
+}
+
+impl<I> Convergents<I> {
+    pub fn new(terms: I) -> Convergents<I> {
+        Convergents {
+            terms,
+            h: (BigInt::one(), BigInt::zero()),
+            k: (BigInt::zero(), BigInt::one()),
+        }
+    }
+}
+
+impl<I> Iterator for Convergents<I>
+where
+    I: Iterator,
+    I::Item: Into<BigInt>,
+{
+    type Item = BigRational;
+    fn next(&mut self) -> Option<Self::Item> {
+        let a: BigInt = self.terms.next()?.into();
+        let h = &a * &self.h.0 + &self.h.1;
+        let k = &a * &self.k.0 + &self.k.1;
+        self.h.1 = std::mem::replace(&mut self.h.0, h);
+        self.k.1 = std::mem::replace(&mut self.k.0, k);
+        Some(BigRational::new_raw(self.h.0.clone(), self.k.0.clone()))
+    }
+}
+
+pub fn convergents<I>(terms: I) -> Convergents<I::IntoIter>
+where
+    I: IntoIterator,
+    I::Item: Into<BigInt>,
+{
+    Convergents::new(terms.into_iter())
+}
+
+// Convergents of sqrt(n), endless unless n is a perfect square
+pub fn sqrt_convergents(n: u64) -> Convergents<impl Iterator<Item = u64>> {
+    let (a0, period) = sqrt_continued_fraction(n);
+    let tail: Box<dyn Iterator<Item = u64>> = if period.is_empty() {
+        Box::new(std::iter::empty())
+    } else {
+        Box::new(period.into_iter().cycle())
+    };
+    convergents(std::iter::once(a0).chain(tail))
+}
+
+// Fundamental (smallest positive) solution of x^2 - d * y^2 = 1, the first
+// convergent of sqrt(d) that satisfies it. None if d is a perfect square.
+pub fn pell(d: u64) -> Option<(BigInt, BigInt)> {
+    let big_d = BigInt::from(d);
+    sqrt_convergents(d)
+        .map(|c| (c.numer().clone(), c.denom().clone()))
+        .find(|(x, y)| x * x - &big_d * y * y == BigInt::one())
+}
+
+// Every positive solution of x^2 - d * y^2 = 1 in increasing order, starting
+// with the fundamental one
+pub fn pell_solutions(d: u64) -> Option<PellSolutions> {
+    let unit = pell(d)?;
+    Some(PellSolutions::new(d, unit.clone(), vec![unit]))
+}
+
+// Fundamental solutions of x^2 - d * y^2 = n, one per class. Every solution is
+// one of these times a power of the fundamental solution of the n = 1 equation.
+// Uses Nagell's bounds on y, so the search is proportional to
+// sqrt(|n| * x1 / d) where x1 is the fundamental solution for n = 1.
+pub fn pell_general_fundamental(d: u64, n: i64) -> Option<Vec<(BigInt, BigInt)>> {
+    let (x1, _) = pell(d)?;
+    let big_d = BigInt::from(d);
+    let big_n = BigInt::from(n);
+    let y_max = if n > 0 {
+        &big_n * (&x1 - 1u32) / (2u32 * &big_d)
+    } else {
+        -&big_n * (&x1 + 1u32) / (2u32 * &big_d)
+    }
+    .sqrt();
+    let mut fundamental = Vec::new();
+    let mut y = BigInt::zero();
+    while y <= y_max {
+        let x2 = &big_d * &y * &y + &big_n;
+        if !x2.is_negative() {
+            let x = x2.sqrt();
+            if &x * &x == x2 {
+                if !x.is_zero() && !y.is_zero() {
+                    fundamental.push((-x.clone(), y.clone()));
+                }
+                fundamental.push((x, y.clone()));
+            }
+        }
+        y += 1u32;
+    }
+    Some(fundamental)
+}
+
+// Every non-negative solution of x^2 - d * y^2 = n in increasing order
+pub fn pell_general(d: u64, n: i64) -> Option<PellSolutions> {
+    let unit = pell(d)?;
+    let fundamental = pell_general_fundamental(d, n)?;
+    Some(PellSolutions::new(d, unit, fundamental))
+}
+
+// Walks each class of solutions by repeatedly multiplying by the fundamental
+// unit, x + y * sqrt(d), merging the classes by size
+#[derive(Debug)]
+pub struct PellSolutions {
+    d: BigInt,
+    unit: (BigInt, BigInt),
+    classes: Vec<(BigInt, BigInt)>,
+    last: Option<(BigInt, BigInt)>,
+}
+
+impl PellSolutions {
+    fn new(d: u64, unit: (BigInt, BigInt), classes: Vec<(BigInt, BigInt)>) -> PellSolutions {
+        PellSolutions {
+            d: BigInt::from(d),
+            unit,
+            classes,
+            last: None,
+        }
+    }
+}
+
+impl Iterator for PellSolutions {
+    type Item = (BigInt, BigInt);
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let (i, _) = self
+                .classes
+                .iter()
+                .enumerate()
+                .min_by(|a, b| a.1 .0.cmp(&b.1 .0))?;
+            let (x, y) = self.classes[i].clone();
+            let (ux, uy) = &self.unit;
+            let mut next = (&x * ux + &self.d * &y * uy, &x * uy + &y * ux);
+            // -(x + y * sqrt(d)) is a solution too, keep the class positive
+            if next.0.is_negative() && next.1.is_negative() {
+                next = (-next.0, -next.1);
+            }
+            let solution = (x, y);
+            // Only (0, 0), from n = 0, maps to itself; drop it once it's out
+            // rather than skipping it as a repeat forever
+            if next == solution {
+                self.classes.swap_remove(i);
+            } else {
+                self.classes[i] = next;
+            }
+            // Ambiguous classes can meet, so skip repeats
+            if !solution.0.is_negative()
+                && !solution.1.is_negative()
+                && self.last.as_ref() != Some(&solution)
+            {
+                self.last = Some(solution.clone());
+                return Some(solution);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use num::rational::Ratio;
+
+    fn big(pairs: &[(i64, i64)]) -> Vec<(BigInt, BigInt)> {
+        pairs
+            .iter()
+            .map(|&(x, y)| (BigInt::from(x), BigInt::from(y)))
+            .collect()
+    }
+
+    #[test]
+    fn test_continued_fraction() {
+        assert_eq!(continued_fraction(415, 93), vec![4, 2, 6, 7]);
+        assert_eq!(continued_fraction(-415, 93), vec![-5, 1, 1, 6, 7]);
+        assert_eq!(continued_fraction(3, 1), vec![3]);
+    }
+
+    #[test]
+    fn test_sqrt_continued_fraction() {
+        assert_eq!(sqrt_continued_fraction(2), (1, vec![2]));
+        assert_eq!(sqrt_continued_fraction(23), (4, vec![1, 3, 1, 8]));
+        assert_eq!(sqrt_continued_fraction(13), (3, vec![1, 1, 1, 1, 6]));
+        assert_eq!(sqrt_continued_fraction(16), (4, vec![]));
+    }
+
+    #[test]
+    fn test_convergents() {
+        let r = |n: i64, d: i64| Ratio::new(BigInt::from(n), BigInt::from(d));
+        assert_eq!(
+            sqrt_convergents(2).take(4).collect::<Vec<_>>(),
+            vec![r(1, 1), r(3, 2), r(7, 5), r(17, 12)]
+        );
+        assert_eq!(convergents(vec![4, 2, 6, 7]).last(), Some(r(415, 93)));
+        assert_eq!(sqrt_convergents(9).collect::<Vec<_>>(), vec![r(3, 1)]);
+    }
+
+    #[test]
+    fn test_pell() {
+        assert_eq!(pell(2), Some(big(&[(3, 2)])[0].clone()));
+        assert_eq!(pell(13), Some(big(&[(649, 180)])[0].clone()));
+        assert_eq!(pell(61), Some(big(&[(1766319049, 226153980)])[0].clone()));
+        assert_eq!(pell(4), None);
+        assert_eq!(
+            pell_solutions(2).unwrap().take(3).collect::<Vec<_>>(),
+            big(&[(3, 2), (17, 12), (99, 70)])
+        );
+    }
+
+    #[test]
+    fn test_pell_general() {
+        // x^2 - 2y^2 = -1
+        assert_eq!(
+            pell_general(2, -1).unwrap().take(3).collect::<Vec<_>>(),
+            big(&[(1, 1), (7, 5), (41, 29)])
+        );
+        // x^2 - 2y^2 = 7 has two classes
+        assert_eq!(
+            pell_general(2, 7).unwrap().take(4).collect::<Vec<_>>(),
+            big(&[(3, 1), (5, 3), (13, 9), (27, 19)])
+        );
+        // x^2 - 2y^2 = 2 has a single ambiguous class
+        assert_eq!(
+            pell_general(2, 2).unwrap().take(3).collect::<Vec<_>>(),
+            big(&[(2, 1), (10, 7), (58, 41)])
+        );
+        // x^2 - 3y^2 = -1 has no solutions
+        assert_eq!(pell_general(3, -1).unwrap().next(), None);
+        // x^2 - 2y^2 = 0 only has (0, 0), and mustn't loop looking for more
+        assert_eq!(
+            pell_general(2, 0).unwrap().take(2).collect::<Vec<_>>(),
+            big(&[(0, 0)])
+        );
+    }
+}
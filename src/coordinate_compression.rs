@@ -0,0 +1,210 @@
+use crate::point::Point;
+use num::{One, Zero};
+use std::ops::{Add, Mul, Sub};
+
+// Maps the x and y values that appear in a set of points to dense indices
+// 0..width and 0..height, keeping their order, so a grid over them stays small
+// however far apart the real coordinates are.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CoordinateCompressor<T> {
+    xs: Vec<T>,
+    ys: Vec<T>,
+}
+
+impl<T> CoordinateCompressor<T>
+where
+    T: Copy + Ord + One + Add<Output = T> + Sub<Output = T>,
+{
+    pub fn new<I: IntoIterator<Item = Point<T>>>(points: I) -> CoordinateCompressor<T> {
+        let (xs, ys) = points.into_iter().map(|p| (p.x, p.y)).unzip();
+        CoordinateCompressor::from_values(xs, ys)
+    }
+
+    // Also keep the values one either side of each point, so the space just
+    // beyond an edge gets its own index
+    pub fn with_neighbours<I: IntoIterator<Item = Point<T>>>(points: I) -> CoordinateCompressor<T> {
+        let mut xs = Vec::new();
+        let mut ys = Vec::new();
+        for p in points {
+            xs.extend([p.x - T::one(), p.x, p.x + T::one()]);
+            ys.extend([p.y - T::one(), p.y, p.y + T::one()]);
+        }
+        CoordinateCompressor::from_values(xs, ys)
+    }
+
+    pub fn from_values(mut xs: Vec<T>, mut ys: Vec<T>) -> CoordinateCompressor<T> {
+        xs.sort_unstable();
+        xs.dedup();
+        ys.sort_unstable();
+        ys.dedup();
+        CoordinateCompressor { xs, ys }
+    }
+
+    // Number of distinct x values
+    pub fn width(&self) -> usize {
+        self.xs.len()
+    }
+
+    // Number of distinct y values
+    pub fn height(&self) -> usize {
+        self.ys.len()
+    }
+
+    pub fn xs(&self) -> &[T] {
+        &self.xs
+    }
+
+    pub fn ys(&self) -> &[T] {
+        &self.ys
+    }
+
+    pub fn x_index(&self, x: T) -> Option<usize> {
+        self.xs.binary_search(&x).ok()
+    }
+
+    pub fn y_index(&self, y: T) -> Option<usize> {
+        self.ys.binary_search(&y).ok()
+    }
+
+    // None if either value wasn't one of the originals
+    pub fn compress(&self, p: &Point<T>) -> Option<Point<usize>> {
+        Some(Point {
+            x: self.x_index(p.x)?,
+            y: self.y_index(p.y)?,
+        })
+    }
+
+    // Panics if the index is out of range
+    pub fn decompress(&self, p: &Point<usize>) -> Point<T> {
+        Point {
+            x: self.xs[p.x],
+            y: self.ys[p.y],
+        }
+    }
+
+    // A grid with a cell for every value and for every gap between them
+    pub fn grid(&self) -> CompressedGrid<'_, T> {
+        CompressedGrid { compressor: self }
+    }
+}
+
+// Cells over a CoordinateCompressor. Value i sits in cell 2i + 1, the even
+// cells are the gaps between values, and cells 0 and the last are a border so
+// a flood fill from (0, 0) can get all the way round. Each cell knows how many
+// real units it covers, with the border counted as one.
+#[derive(Debug, Copy, Clone)]
+pub struct CompressedGrid<'a, T> {
+    compressor: &'a CoordinateCompressor<T>,
+}
+
+impl<T> CompressedGrid<'_, T>
+where
+    T: Copy + Ord + Zero + One + Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
+{
+    // Number of columns of cells
+    pub fn width(&self) -> usize {
+        2 * self.compressor.width() + 1
+    }
+
+    // Number of rows of cells
+    pub fn height(&self) -> usize {
+        2 * self.compressor.height() + 1
+    }
+
+    // The cell holding a point, None if either value wasn't one of the
+    // originals
+    pub fn cell(&self, p: &Point<T>) -> Option<Point<usize>> {
+        self.compressor.compress(p).map(|c| Point {
+            x: 2 * c.x + 1,
+            y: 2 * c.y + 1,
+        })
+    }
+
+    // The real point of a value cell, None for a gap
+    pub fn point(&self, cell: &Point<usize>) -> Option<Point<T>> {
+        (cell.x % 2 == 1 && cell.y % 2 == 1).then(|| {
+            self.compressor.decompress(&Point {
+                x: cell.x / 2,
+                y: cell.y / 2,
+            })
+        })
+    }
+
+    // Real columns in a column of cells, zero for a gap between adjacent values
+    pub fn cell_width(&self, col: usize) -> T {
+        cell_size(&self.compressor.xs, col)
+    }
+
+    // Real rows in a row of cells
+    pub fn cell_height(&self, row: usize) -> T {
+        cell_size(&self.compressor.ys, row)
+    }
+
+    pub fn cell_area(&self, cell: &Point<usize>) -> T {
+        self.cell_width(cell.x) * self.cell_height(cell.y)
+    }
+}
+
+fn cell_size<T>(values: &[T], i: usize) -> T
+where
+    T: Copy + Zero + One + Sub<Output = T>,
+{
+    if i % 2 == 1 || i == 0 || i == 2 * values.len() {
+        T::one()
+    } else {
+        values[i / 2] - values[i / 2 - 1] - T::one()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::coordinate_compression::CoordinateCompressor;
+    use crate::point::Point;
+
+    #[test]
+    fn test_compress() {
+        let points = vec![
+            Point { x: 100, y: -5 },
+            Point { x: 7, y: 1000 },
+            Point { x: 100, y: 3 },
+        ];
+        let cc = CoordinateCompressor::new(points.clone());
+        assert_eq!((cc.width(), cc.height()), (2, 3));
+        assert_eq!(cc.xs(), &[7, 100]);
+        assert_eq!(cc.compress(&points[1]), Some(Point { x: 0, y: 2 }));
+        assert_eq!(cc.compress(&Point { x: 8, y: 3 }), None);
+        for p in &points {
+            assert_eq!(cc.decompress(&cc.compress(p).unwrap()), *p);
+        }
+
+        let cc =
+            CoordinateCompressor::with_neighbours(vec![Point { x: 0, y: 0 }, Point { x: 2, y: 9 }]);
+        assert_eq!(cc.xs(), &[-1, 0, 1, 2, 3]);
+        assert_eq!(cc.ys(), &[-1, 0, 1, 8, 9, 10]);
+    }
+
+    #[test]
+    fn test_grid() {
+        let cc = CoordinateCompressor::new(vec![Point { x: 2, y: 10 }, Point { x: 3, y: 20 }]);
+        let grid = cc.grid();
+        assert_eq!((grid.width(), grid.height()), (5, 5));
+        assert_eq!(
+            grid.cell(&Point { x: 3, y: 20 }),
+            Some(Point { x: 3, y: 3 })
+        );
+        assert_eq!(
+            grid.point(&Point { x: 1, y: 3 }),
+            Some(Point { x: 2, y: 20 })
+        );
+        assert_eq!(grid.point(&Point { x: 2, y: 3 }), None);
+        // Adjacent x values leave an empty gap, the y gap is 11..=19
+        assert_eq!(grid.cell_width(2), 0);
+        assert_eq!(grid.cell_height(2), 9);
+        assert_eq!(grid.cell_area(&Point { x: 1, y: 2 }), 9);
+        assert_eq!(grid.cell_width(0), 1);
+        // Every real point between the outer values is in exactly one cell
+        let total: i64 = (1..4).map(|c| grid.cell_width(c)).sum::<i64>()
+            * (1..4).map(|r| grid.cell_height(r)).sum::<i64>();
+        assert_eq!(total, 2 * 11);
+    }
+}
@@ -0,0 +1,247 @@
+use crate::interval_set::IntervalSet;
+use crate::point::Point;
+use crate::rect::Rect;
+use num::{Integer, Signed};
+use std::ops::Range;
+
+// Every point within taxicab distance radius of centre, eg. the area a sensor
+// rules out as far as its closest beacon.
+#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq)]
+pub struct Diamond<T> {
+    pub centre: Point<T>,
+    pub radius: T,
+}
+
+// Rotate 45 degrees to u = x + y, v = x - y, where taxicab distance becomes
+// Chebyshev distance and every diamond becomes a square. Only points with u
+// and v both odd or both even come back from from_chebyshev.
+pub fn to_chebyshev<T: Integer + Copy>(p: Point<T>) -> Point<T> {
+    Point {
+        x: p.x + p.y,
+        y: p.x - p.y,
+    }
+}
+
+pub fn from_chebyshev<T: Integer + Copy>(p: Point<T>) -> Option<Point<T>> {
+    let two = T::one() + T::one();
+    let (x, rem) = (p.x + p.y).div_rem(&two);
+    rem.is_zero().then(|| Point { x, y: p.x - x })
+}
+
+impl<T: Integer + Signed + Copy> Diamond<T> {
+    pub fn new(centre: Point<T>, radius: T) -> Diamond<T> {
+        Diamond { centre, radius }
+    }
+
+    // Centred on one point and just reaching the other
+    pub fn from_points(centre: Point<T>, edge: Point<T>) -> Diamond<T> {
+        Diamond {
+            centre,
+            radius: centre.taxicab_distance(edge),
+        }
+    }
+
+    pub fn contains(&self, p: &Point<T>) -> bool {
+        self.centre.taxicab_distance(*p) <= self.radius
+    }
+
+    // The x values covered in row y, None if the row misses
+    pub fn row(&self, y: T) -> Option<Range<T>> {
+        let half = self.radius - (y - self.centre.y).abs();
+        (!half.is_negative()).then(|| self.centre.x - half..self.centre.x + half + T::one())
+    }
+
+    // The square this becomes under to_chebyshev, though only its points with
+    // u and v of the same parity are real
+    pub fn to_chebyshev(&self) -> Rect<T> {
+        let c = to_chebyshev(self.centre);
+        let r = Point {
+            x: self.radius,
+            y: self.radius,
+        };
+        Rect::inclusive(c - r, c + r)
+    }
+}
+
+// The x values any of the diamonds cover in row y
+pub fn row_coverage<T: Integer + Signed + Copy>(diamonds: &[Diamond<T>], y: T) -> IntervalSet<T> {
+    diamonds.iter().filter_map(|d| d.row(y)).collect()
+}
+
+// A point in bounds that no diamond covers. A lone uncovered point has every
+// neighbour covered, so it sits one or two steps outside some diamond along
+// both diagonals, or on the edge of bounds. Only those spots are tried, so
+// this is quick however large bounds is, and finds the point whenever it's the
+// only one.
+pub fn find_uncovered<T: Integer + Signed + Copy>(
+    diamonds: &[Diamond<T>],
+    bounds: &Rect<T>,
+) -> Option<Point<T>> {
+    if bounds.is_empty() {
+        return None;
+    }
+    let one = T::one();
+    let two = one + one;
+    let mut us = Vec::new();
+    let mut vs = Vec::new();
+    for d in diamonds {
+        let c = to_chebyshev(d.centre);
+        for k in [d.radius + one, d.radius + two] {
+            us.extend([c.x - k, c.x + k]);
+            vs.extend([c.y - k, c.y + k]);
+        }
+    }
+    let (x0, x1) = (bounds.min.x, bounds.max.x - one);
+    let (y0, y1) = (bounds.min.y, bounds.max.y - one);
+
+    let mut candidates = vec![
+        Point { x: x0, y: y0 },
+        Point { x: x1, y: y0 },
+        Point { x: x0, y: y1 },
+        Point { x: x1, y: y1 },
+    ];
+    for &u in &us {
+        for &v in &vs {
+            candidates.extend(from_chebyshev(Point { x: u, y: v }));
+        }
+    }
+    // Where the diagonals cross the edges of bounds
+    for x in [x0, x1] {
+        candidates.extend(us.iter().map(|&u| Point { x, y: u - x }));
+        candidates.extend(vs.iter().map(|&v| Point { x, y: x - v }));
+    }
+    for y in [y0, y1] {
+        candidates.extend(us.iter().map(|&u| Point { x: u - y, y }));
+        candidates.extend(vs.iter().map(|&v| Point { x: v + y, y }));
+    }
+    candidates
+        .into_iter()
+        .find(|p| bounds.contains(p) && !diamonds.iter().any(|d| d.contains(p)))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::diamond::{find_uncovered, from_chebyshev, row_coverage, to_chebyshev, Diamond};
+    use crate::point::Point;
+    use crate::rect::Rect;
+    use crate::test_util::lcg;
+
+    const SENSORS: &str = "Sensor at x=2, y=18: closest beacon is at x=-2, y=15
+Sensor at x=9, y=16: closest beacon is at x=10, y=16
+Sensor at x=13, y=2: closest beacon is at x=15, y=3
+Sensor at x=12, y=14: closest beacon is at x=10, y=16
+Sensor at x=10, y=20: closest beacon is at x=10, y=16
+Sensor at x=14, y=17: closest beacon is at x=10, y=16
+Sensor at x=8, y=7: closest beacon is at x=2, y=10
+Sensor at x=2, y=0: closest beacon is at x=2, y=10
+Sensor at x=0, y=11: closest beacon is at x=2, y=10
+Sensor at x=20, y=14: closest beacon is at x=25, y=17
+Sensor at x=17, y=20: closest beacon is at x=21, y=22
+Sensor at x=16, y=7: closest beacon is at x=15, y=3
+Sensor at x=14, y=3: closest beacon is at x=15, y=3
+Sensor at x=20, y=1: closest beacon is at x=15, y=3";
+
+    fn parse() -> Vec<(Point<i64>, Point<i64>)> {
+        SENSORS
+            .lines()
+            .map(|line| {
+                let v: Vec<i64> = line
+                    .split(['=', ',', ':'])
+                    .filter_map(|s| s.parse().ok())
+                    .collect();
+                (Point { x: v[0], y: v[1] }, Point { x: v[2], y: v[3] })
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_diamond() {
+        let d = Diamond::new(Point { x: 8, y: 7 }, 9);
+        assert_eq!(
+            d,
+            Diamond::from_points(Point { x: 8, y: 7 }, Point { x: 2, y: 10 })
+        );
+        assert!(d.contains(&Point { x: 8, y: -2 }));
+        assert!(!d.contains(&Point { x: 9, y: -2 }));
+        assert_eq!(d.row(10), Some(2..15));
+        assert_eq!(d.row(-2), Some(8..9));
+        assert_eq!(d.row(17), None);
+        // In Chebyshev space the corners are the corners of a square
+        let square = d.to_chebyshev();
+        assert_eq!(square.width(), 19);
+        assert!(square.contains(&to_chebyshev(Point { x: 17, y: 7 })));
+        assert!(!square.contains(&to_chebyshev(Point { x: 18, y: 7 })));
+    }
+
+    #[test]
+    fn test_chebyshev() {
+        let p = Point { x: 3, y: -7 };
+        assert_eq!(to_chebyshev(p), Point { x: -4, y: 10 });
+        assert_eq!(from_chebyshev(to_chebyshev(p)), Some(p));
+        assert_eq!(from_chebyshev(Point { x: 1, y: 2 }), None);
+        let q = Point { x: -1, y: 5 };
+        assert_eq!(
+            p.taxicab_distance(q),
+            to_chebyshev(p).chebyshev_distance(to_chebyshev(q))
+        );
+    }
+
+    #[test]
+    fn test_beacon_exclusion() {
+        let readings = parse();
+        let diamonds: Vec<Diamond<i64>> = readings
+            .iter()
+            .map(|&(s, b)| Diamond::from_points(s, b))
+            .collect();
+        let covered = row_coverage(&diamonds, 10);
+        assert_eq!(covered.ranges().len(), 1);
+        assert_eq!(covered.ranges()[0], -2..25);
+        let mut beacons: Vec<Point<i64>> = readings.iter().map(|&(_, b)| b).collect();
+        beacons.sort();
+        beacons.dedup();
+        let in_row = beacons.iter().filter(|b| b.y == 10).count() as i64;
+        assert_eq!(covered.covered_len() - in_row, 26);
+
+        let bounds = Rect::inclusive(Point { x: 0, y: 0 }, Point { x: 20, y: 20 });
+        assert_eq!(
+            find_uncovered(&diamonds, &bounds),
+            Some(Point { x: 14, y: 11 })
+        );
+        assert_eq!(row_coverage(&diamonds, 11).ranges(), &[-3..14, 15..26]);
+    }
+
+    #[test]
+    fn test_find_uncovered_against_brute_force() {
+        // Pseudo-random diamonds over a small square, checked wherever they
+        // leave exactly one gap
+        let mut rand = lcg(12345);
+        let bounds = Rect::from_size(6, 6);
+        let mut lone_gaps = 0;
+        for _ in 0..5000 {
+            let count = 2 + rand(5);
+            let diamonds: Vec<Diamond<i64>> = (0..count)
+                .map(|_| {
+                    let centre = Point {
+                        x: rand(10) - 2,
+                        y: rand(10) - 2,
+                    };
+                    Diamond::new(centre, 1 + rand(4))
+                })
+                .collect();
+            let uncovered: Vec<Point<i64>> = bounds
+                .points()
+                .filter(|p| !diamonds.iter().any(|d| d.contains(p)))
+                .collect();
+            let found = find_uncovered(&diamonds, &bounds);
+            match uncovered.len() {
+                0 => assert_eq!(found, None),
+                1 => {
+                    assert_eq!(found, Some(uncovered[0]), "{:?}", diamonds);
+                    lone_gaps += 1;
+                }
+                _ => assert!(found.is_none_or(|p| uncovered.contains(&p))),
+            }
+        }
+        assert!(lone_gaps > 50, "{}", lone_gaps);
+    }
+}
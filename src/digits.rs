@@ -0,0 +1,229 @@
+use num::{pow, CheckedAdd, CheckedMul, FromPrimitive, Integer, ToPrimitive};
+
+// Digits of a non-negative integer in any base, least significant first.
+// Zero has a single digit, 0.
+#[derive(Debug)]
+pub struct Digits<T> {
+    n: T,
+    base: T,
+    done: bool,
+}
+
+impl<T: Integer + Copy> Digits<T> {
+    pub fn new(n: T, base: T) -> Digits<T> {
+        assert!(base > T::one(), "base must be at least 2");
+        Digits {
+            n,
+            base,
+            done: false,
+        }
+    }
+}
+
+impl<T: Integer + Copy> Iterator for Digits<T> {
+    type Item = T;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let (rest, digit) = self.n.div_rem(&self.base);
+        self.n = rest;
+        self.done = rest.is_zero();
+        Some(digit)
+    }
+}
+
+// Digits of a non-negative integer in any base, most significant first.
+#[derive(Debug)]
+pub struct DigitsMsb<T> {
+    n: T,
+    base: T,
+    place: T,
+}
+
+impl<T: Integer + Copy> DigitsMsb<T> {
+    pub fn new(n: T, base: T) -> DigitsMsb<T> {
+        assert!(base > T::one(), "base must be at least 2");
+        DigitsMsb {
+            n,
+            base,
+            place: pow(base, count_digits(n, base) - 1),
+        }
+    }
+}
+
+impl<T: Integer + Copy> Iterator for DigitsMsb<T> {
+    type Item = T;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.place.is_zero() {
+            return None;
+        }
+        let (digit, rest) = self.n.div_rem(&self.place);
+        self.n = rest;
+        self.place = self.place / self.base;
+        Some(digit)
+    }
+}
+
+pub fn digits<T: Integer + Copy>(n: T, base: T) -> Digits<T> {
+    Digits::new(n, base)
+}
+
+pub fn digits_msb<T: Integer + Copy>(n: T, base: T) -> DigitsMsb<T> {
+    DigitsMsb::new(n, base)
+}
+
+// Rebuild a number from its digits, most significant first
+pub fn from_digits<T: Integer + Copy>(digits: impl IntoIterator<Item = T>, base: T) -> T {
+    digits
+        .into_iter()
+        .fold(T::zero(), |acc, digit| acc * base + digit)
+}
+
+// Rebuild a number from its digits, least significant first
+pub fn from_digits_lsb<T: Integer + Copy>(digits: impl IntoIterator<Item = T>, base: T) -> T {
+    digits
+        .into_iter()
+        .fold((T::zero(), T::one()), |(acc, place), digit| {
+            (acc + digit * place, place * base)
+        })
+        .0
+}
+
+// Number of digits without going via a String. Zero has one digit.
+pub fn count_digits<T: Integer + Copy>(n: T, base: T) -> usize {
+    let mut n = n / base;
+    let mut count = 1;
+    while !n.is_zero() {
+        n = n / base;
+        count += 1;
+    }
+    count
+}
+
+// Numeric concatenation, eg. 12 || 345 = 12345 in base 10
+pub fn concat<T: Integer + Copy>(a: T, b: T, base: T) -> T {
+    a * pow(base, count_digits(b, base)) + b
+}
+
+// Split into left and right halves by digit count, eg. 253000 -> (253, 0).
+// None if the number has an odd number of digits.
+pub fn split_half<T: Integer + Copy>(n: T, base: T) -> Option<(T, T)> {
+    let count = count_digits(n, base);
+    if count % 2 == 1 {
+        return None;
+    }
+    Some(n.div_rem(&pow(base, count / 2)))
+}
+
+// None if the reversed number doesn't fit in T, eg. 4000000009u32
+pub fn reverse_digits<T>(n: T, base: T) -> Option<T>
+where
+    T: Integer + Copy + CheckedMul + CheckedAdd,
+{
+    digits(n, base).try_fold(T::zero(), |acc, digit| {
+        acc.checked_mul(&base)?.checked_add(&digit)
+    })
+}
+
+// Compares the digits themselves, so works right up to T's maximum
+pub fn is_palindrome<T: Integer + Copy>(n: T, base: T) -> bool {
+    let digits: Vec<T> = digits(n, base).collect();
+    digits.iter().eq(digits.iter().rev())
+}
+
+// Bases 2 to 36, lowercase letters for digits above 9
+pub fn to_base_string<T: Integer + Copy + ToPrimitive>(n: T, base: T) -> String {
+    let radix = base.to_u32().expect("base out of range");
+    assert!((2..=36).contains(&radix), "base must be between 2 and 36");
+    digits_msb(n, base)
+        .map(|d| char::from_digit(d.to_u32().unwrap(), radix).unwrap())
+        .collect()
+}
+
+// Inverse of to_base_string, case-insensitive. None on an empty string, an
+// invalid digit, or a value that doesn't fit in T.
+pub fn from_base_string<T>(s: &str, base: u32) -> Option<T>
+where
+    T: Integer + Copy + FromPrimitive + CheckedMul + CheckedAdd,
+{
+    assert!((2..=36).contains(&base), "base must be between 2 and 36");
+    if s.is_empty() {
+        return None;
+    }
+    let radix = T::from_u32(base)?;
+    s.chars().try_fold(T::zero(), |acc, c| {
+        let digit = T::from_u32(c.to_digit(base)?)?;
+        acc.checked_mul(&radix)?.checked_add(&digit)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_digits() {
+        assert_eq!(digits(1234, 10).collect::<Vec<_>>(), vec![4, 3, 2, 1]);
+        assert_eq!(digits_msb(1234, 10).collect::<Vec<_>>(), vec![1, 2, 3, 4]);
+        assert_eq!(digits(0, 10).collect::<Vec<_>>(), vec![0]);
+        assert_eq!(digits_msb(0, 10).collect::<Vec<_>>(), vec![0]);
+        assert_eq!(digits_msb(6u64, 2).collect::<Vec<_>>(), vec![1, 1, 0]);
+    }
+
+    #[test]
+    fn test_from_digits() {
+        assert_eq!(from_digits(vec![1, 2, 3, 4], 10), 1234);
+        assert_eq!(from_digits_lsb(vec![4, 3, 2, 1], 10), 1234);
+        assert_eq!(from_digits(digits_msb(0xbeefu32, 16), 16), 0xbeef);
+    }
+
+    #[test]
+    fn test_count_digits() {
+        assert_eq!(count_digits(0, 10), 1);
+        assert_eq!(count_digits(9, 10), 1);
+        assert_eq!(count_digits(10, 10), 2);
+        assert_eq!(count_digits(u64::MAX, 10), 20);
+        assert_eq!(count_digits(255, 2), 8);
+    }
+
+    #[test]
+    fn test_concat() {
+        assert_eq!(concat(12, 345, 10), 12345);
+        assert_eq!(concat(12, 0, 10), 120);
+        assert_eq!(concat(0b10, 0b11, 2), 0b1011);
+    }
+
+    #[test]
+    fn test_split_half() {
+        assert_eq!(split_half(253000, 10), Some((253, 0)));
+        assert_eq!(split_half(1000, 10), Some((10, 0)));
+        assert_eq!(split_half(17, 10), Some((1, 7)));
+        assert_eq!(split_half(125, 10), None);
+    }
+
+    #[test]
+    fn test_palindrome() {
+        assert_eq!(reverse_digits(1230, 10), Some(321));
+        assert!(is_palindrome(9009, 10));
+        assert!(!is_palindrome(9008, 10));
+        assert!(is_palindrome(585, 2));
+        // Near the maximum, where the reverse can overflow
+        assert_eq!(reverse_digits(4_000_000_009u32, 10), None);
+        assert_eq!(reverse_digits(4_000_000_003u32, 10), Some(3_000_000_004));
+        assert!(!is_palindrome(u64::MAX, 10));
+        assert!(is_palindrome(u64::MAX, 2));
+        assert!(is_palindrome(4_294_884_924u32, 10));
+    }
+
+    #[test]
+    fn test_base_string() {
+        assert_eq!(to_base_string(255, 16), "ff");
+        assert_eq!(to_base_string(5, 2), "101");
+        assert_eq!(to_base_string(0, 36), "0");
+        assert_eq!(from_base_string::<u32>("FF", 16), Some(255));
+        assert_eq!(from_base_string::<u8>("100", 16), None);
+        assert_eq!(from_base_string::<u32>("12z", 10), None);
+        assert_eq!(from_base_string::<u32>("", 10), None);
+    }
+}
@@ -0,0 +1,234 @@
+use crate::point::Point;
+use num::Signed;
+use std::fmt;
+use std::str::FromStr;
+
+// Screen coordinates, as Point::cardinal_points: North is y - 1.
+
+// The 4 cardinal directions, clockwise in the same order as cardinal_points
+#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq, Ord, PartialOrd)]
+pub enum Direction {
+    North,
+    East,
+    South,
+    West,
+}
+
+// All 8 compass directions, clockwise in the same order as compass_points
+#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq, Ord, PartialOrd)]
+pub enum Direction8 {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseDirectionError(pub String);
+
+impl fmt::Display for ParseDirectionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "not a direction: {:?}", self.0)
+    }
+}
+
+impl std::error::Error for ParseDirectionError {}
+
+impl Direction {
+    pub const ALL: [Direction; 4] = [
+        Direction::North,
+        Direction::East,
+        Direction::South,
+        Direction::West,
+    ];
+
+    fn rotate(self, quarter_turns: usize) -> Direction {
+        Direction::ALL[(self as usize + quarter_turns) % 4]
+    }
+
+    pub fn turn_right(self) -> Direction {
+        self.rotate(1)
+    }
+
+    pub fn opposite(self) -> Direction {
+        self.rotate(2)
+    }
+
+    pub fn turn_left(self) -> Direction {
+        self.rotate(3)
+    }
+
+    // Signed only, as North and West need -1. Point<usize> has its own
+    // neighbour and step methods.
+    pub fn offset<T: Signed>(self) -> Point<T> {
+        Direction8::from(self).offset()
+    }
+}
+
+impl Direction8 {
+    pub const ALL: [Direction8; 8] = [
+        Direction8::North,
+        Direction8::NorthEast,
+        Direction8::East,
+        Direction8::SouthEast,
+        Direction8::South,
+        Direction8::SouthWest,
+        Direction8::West,
+        Direction8::NorthWest,
+    ];
+
+    fn rotate(self, eighth_turns: usize) -> Direction8 {
+        Direction8::ALL[(self as usize + eighth_turns) % 8]
+    }
+
+    // 45 degrees clockwise
+    pub fn turn_right(self) -> Direction8 {
+        self.rotate(1)
+    }
+
+    pub fn opposite(self) -> Direction8 {
+        self.rotate(4)
+    }
+
+    // 45 degrees anticlockwise
+    pub fn turn_left(self) -> Direction8 {
+        self.rotate(7)
+    }
+
+    pub fn offset<T: Signed>(self) -> Point<T> {
+        let minus_one = || -T::one();
+        let (x, y) = match self {
+            Direction8::North => (T::zero(), minus_one()),
+            Direction8::NorthEast => (T::one(), minus_one()),
+            Direction8::East => (T::one(), T::zero()),
+            Direction8::SouthEast => (T::one(), T::one()),
+            Direction8::South => (T::zero(), T::one()),
+            Direction8::SouthWest => (minus_one(), T::one()),
+            Direction8::West => (minus_one(), T::zero()),
+            Direction8::NorthWest => (minus_one(), minus_one()),
+        };
+        Point { x, y }
+    }
+}
+
+impl From<Direction> for Direction8 {
+    fn from(dir: Direction) -> Self {
+        match dir {
+            Direction::North => Direction8::North,
+            Direction::East => Direction8::East,
+            Direction::South => Direction8::South,
+            Direction::West => Direction8::West,
+        }
+    }
+}
+
+// Arrows '^' '>' 'v' '<', 'U' 'R' 'D' 'L' or 'N' 'E' 'S' 'W'
+impl TryFrom<char> for Direction {
+    type Error = ParseDirectionError;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        match c {
+            '^' | 'U' | 'N' => Ok(Direction::North),
+            '>' | 'R' | 'E' => Ok(Direction::East),
+            'v' | 'D' | 'S' => Ok(Direction::South),
+            '<' | 'L' | 'W' => Ok(Direction::West),
+            _ => Err(ParseDirectionError(c.to_string())),
+        }
+    }
+}
+
+impl FromStr for Direction {
+    type Err = ParseDirectionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Direction::try_from(c),
+            _ => Err(ParseDirectionError(s.to_string())),
+        }
+    }
+}
+
+// "N", "NE", "E", ... as well as anything a Direction parses from
+impl FromStr for Direction8 {
+    type Err = ParseDirectionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "NE" => Ok(Direction8::NorthEast),
+            "SE" => Ok(Direction8::SouthEast),
+            "SW" => Ok(Direction8::SouthWest),
+            "NW" => Ok(Direction8::NorthWest),
+            _ => s.parse::<Direction>().map(Direction8::from),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::direction::{Direction, Direction8, ParseDirectionError};
+    use crate::point::Point;
+
+    #[test]
+    fn test_turns() {
+        assert_eq!(Direction::North.turn_right(), Direction::East);
+        assert_eq!(Direction::North.turn_left(), Direction::West);
+        assert_eq!(Direction::West.turn_right(), Direction::North);
+        assert_eq!(Direction::East.opposite(), Direction::West);
+        assert_eq!(Direction8::North.turn_left(), Direction8::NorthWest);
+        assert_eq!(Direction8::NorthWest.turn_right(), Direction8::North);
+        assert_eq!(Direction8::SouthEast.opposite(), Direction8::NorthWest);
+    }
+
+    #[test]
+    fn test_offsets_match_points() {
+        let origin: Point<i32> = Point { x: 0, y: 0 };
+        let cardinal: Vec<Point<i32>> = Direction::ALL.iter().map(|d| d.offset()).collect();
+        assert_eq!(cardinal, origin.cardinal_points());
+        let compass: Vec<Point<i32>> = Direction8::ALL.iter().map(|d| d.offset()).collect();
+        assert_eq!(compass, origin.compass_points());
+    }
+
+    #[test]
+    fn test_parse() {
+        let arrows: Vec<Direction> = "^>v<"
+            .chars()
+            .map(|c| Direction::try_from(c).unwrap())
+            .collect();
+        assert_eq!(arrows, Direction::ALL);
+        assert_eq!("U".parse(), Ok(Direction::North));
+        assert_eq!("L".parse(), Ok(Direction::West));
+        assert_eq!("S".parse(), Ok(Direction::South));
+        assert_eq!("NE".parse(), Ok(Direction8::NorthEast));
+        assert_eq!("v".parse(), Ok(Direction8::South));
+        assert_eq!(
+            "X".parse::<Direction>(),
+            Err(ParseDirectionError("X".to_string()))
+        );
+        assert!("NE".parse::<Direction>().is_err());
+    }
+
+    #[test]
+    fn test_step() {
+        let pt: Point<i64> = Point { x: 3, y: 4 };
+        assert_eq!(pt.step(Direction::North, 3), Point { x: 3, y: 1 });
+        assert_eq!(pt.step(Direction::West, 1), Point { x: 2, y: 4 });
+        assert_eq!(pt.step(Direction8::SouthEast, 2), Point { x: 5, y: 6 });
+
+        let pt: Point<usize> = Point { x: 3, y: 3 };
+        assert_eq!(
+            pt.checked_step(Direction::North, 1),
+            Some(Point { x: 3, y: 2 })
+        );
+        assert_eq!(pt.checked_step(Direction8::NorthWest, 4), None);
+        assert_eq!(
+            pt.checked_step(Direction::East, 5),
+            Some(Point { x: 8, y: 3 })
+        );
+        assert_eq!(pt.checked_step(Direction::South, usize::MAX), None);
+    }
+}
@@ -0,0 +1,205 @@
+// Linear algebra over GF(2), where addition is XOR. Each row is packed into a
+// u128 so there can be at most 128 columns (variables), and the right hand
+// side is packed the same way so at most 128 rows.
+//
+// For a lights-out style puzzle the columns are buttons and the rows are
+// lights: button j toggles light i when bit j of row i is set.
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Gf2Matrix {
+    rows: Vec<u128>,
+    cols: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Gf2Solution {
+    // One solution, with every free variable set to zero
+    pub particular: u128,
+    // Basis of the null space, one vector per free variable
+    pub null_space: Vec<u128>,
+}
+
+impl Gf2Matrix {
+    pub fn from_rows(rows: Vec<u128>, cols: usize) -> Gf2Matrix {
+        assert!(cols <= 128, "at most 128 columns");
+        assert!(rows.len() <= 128, "at most 128 rows");
+        Gf2Matrix { rows, cols }
+    }
+
+    // Build from button -> lights masks, bit i of columns[j] set when button j
+    // toggles light i
+    pub fn from_columns(columns: &[u128], rows: usize) -> Gf2Matrix {
+        // Checked before packing, as the shifts overflow past 128
+        assert!(columns.len() <= 128, "at most 128 columns");
+        assert!(rows <= 128, "at most 128 rows");
+        let packed = (0..rows)
+            .map(|i| {
+                columns
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, &col)| col >> i & 1 == 1)
+                    .fold(0, |row, (j, _)| row | 1 << j)
+            })
+            .collect();
+        Gf2Matrix::from_rows(packed, columns.len())
+    }
+
+    pub fn rank(&self) -> usize {
+        self.eliminate(0).pivots.len()
+    }
+
+    // Reduced row echelon form of the augmented matrix [A | b]. The right hand
+    // side is carried alongside as one bit per row.
+    fn eliminate(&self, b: u128) -> Echelon {
+        let mut rows: Vec<(u128, bool)> = self
+            .rows
+            .iter()
+            .enumerate()
+            .map(|(i, &row)| (row, b >> i & 1 == 1))
+            .collect();
+        let mut pivots = Vec::new();
+        for col in 0..self.cols {
+            let r = pivots.len();
+            let Some(pivot) = (r..rows.len()).find(|&i| rows[i].0 >> col & 1 == 1) else {
+                continue;
+            };
+            rows.swap(r, pivot);
+            let (pivot_row, pivot_rhs) = rows[r];
+            for (i, row) in rows.iter_mut().enumerate() {
+                if i != r && row.0 >> col & 1 == 1 {
+                    row.0 ^= pivot_row;
+                    row.1 ^= pivot_rhs;
+                }
+            }
+            pivots.push(col);
+        }
+        Echelon { rows, pivots }
+    }
+
+    // Solve Ax = b, where bit i of b is the target for row i. None if there is
+    // no solution.
+    pub fn solve(&self, b: u128) -> Option<Gf2Solution> {
+        let Echelon { rows, pivots } = self.eliminate(b);
+        // Remaining zero rows must have a zero right hand side
+        if rows[pivots.len()..].iter().any(|&(_, rhs)| rhs) {
+            return None;
+        }
+        let particular = pivots
+            .iter()
+            .zip(&rows)
+            .filter(|(_, &(_, rhs))| rhs)
+            .fold(0, |x, (&col, _)| x | 1 << col);
+        let null_space = (0..self.cols)
+            .filter(|col| !pivots.contains(col))
+            .map(|free| {
+                pivots
+                    .iter()
+                    .zip(&rows)
+                    .filter(|(_, &(row, _))| row >> free & 1 == 1)
+                    .fold(1 << free, |x, (&col, _)| x | 1 << col)
+            })
+            .collect();
+        Some(Gf2Solution {
+            particular,
+            null_space,
+        })
+    }
+
+    // The solution with the fewest bits set, eg. the fewest button presses
+    pub fn min_weight_solution(&self, b: u128) -> Option<u128> {
+        self.solve(b).map(|solution| solution.min_weight())
+    }
+}
+
+struct Echelon {
+    rows: Vec<(u128, bool)>,
+    pivots: Vec<usize>,
+}
+
+impl Gf2Solution {
+    // Every solution, 2^(null space dimension) of them. Walks a Gray code so
+    // each step is a single XOR: step i flips the null space vector numbered
+    // by the trailing zeros of i, and the first i without one is 2^dimension.
+    // Counting that way rather than up to 2^dimension still works when the
+    // dimension is the full 128.
+    pub fn solutions(&self) -> impl Iterator<Item = u128> + '_ {
+        let mut x = self.particular;
+        std::iter::once(x).chain((1..=u128::MAX).map_while(move |i| {
+            let v = self.null_space.get(i.trailing_zeros() as usize)?;
+            x ^= v;
+            Some(x)
+        }))
+    }
+
+    // Tries every solution, so this is exponential in the null space
+    // dimension. Stops early if it finds the all zero solution.
+    pub fn min_weight(&self) -> u128 {
+        let mut best = self.particular;
+        for x in self.solutions() {
+            if x.count_ones() < best.count_ones() {
+                best = x;
+            }
+            if best == 0 {
+                break;
+            }
+        }
+        best
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rank() {
+        let m = Gf2Matrix::from_rows(vec![0b011, 0b110, 0b101], 3);
+        assert_eq!(m.rank(), 2);
+        let m = Gf2Matrix::from_rows(vec![0b001, 0b010, 0b100], 3);
+        assert_eq!(m.rank(), 3);
+    }
+
+    #[test]
+    fn test_from_columns() {
+        // Button 0 toggles lights 0 & 1, button 1 toggles light 1
+        let m = Gf2Matrix::from_columns(&[0b11, 0b10], 2);
+        assert_eq!(m, Gf2Matrix::from_rows(vec![0b01, 0b11], 2));
+    }
+
+    #[test]
+    fn test_solve() {
+        let m = Gf2Matrix::from_rows(vec![0b011, 0b110, 0b101], 3);
+        let solution = m.solve(0b011).unwrap();
+        assert_eq!(solution.null_space.len(), 1);
+        for x in solution.solutions() {
+            let lights = (0..3).fold(0u128, |b, i| {
+                b | (((m.rows[i] & x).count_ones() as u128) & 1) << i
+            });
+            assert_eq!(lights, 0b011);
+        }
+        // Rows sum to zero, so the targets must too
+        assert_eq!(m.solve(0b001), None);
+    }
+
+    #[test]
+    fn test_min_weight() {
+        // [.##.] (3) (1,3) (2) (2,3) (0,2) (0,1)
+        let buttons = [0b1000, 0b1010, 0b0100, 0b1100, 0b0101, 0b0011];
+        let m = Gf2Matrix::from_columns(&buttons, 4);
+        let best = m.min_weight_solution(0b0110).unwrap();
+        assert_eq!(best.count_ones(), 2);
+        assert_eq!(m.solve(0b0110).unwrap().solutions().count(), 4);
+    }
+
+    #[test]
+    fn test_full_null_space() {
+        // No constraints on 128 unknowns, so all 128 are free
+        let m = Gf2Matrix::from_rows(vec![0], 128);
+        let solution = m.solve(0).unwrap();
+        assert_eq!(solution.null_space.len(), 128);
+        let first: Vec<u128> = solution.solutions().take(4).collect();
+        assert_eq!(first, [0, 1, 3, 2]);
+        assert_eq!(m.min_weight_solution(0), Some(0));
+        assert_eq!(m.solve(1), None);
+    }
+}
@@ -1,8 +1,8 @@
 use std::cmp::{Ordering, Reverse};
 use crate::point::Point;
+use crate::rect::Rect;
 use std::collections::{BinaryHeap, HashMap, HashSet};
 use std::{env, fs};
-use std::ops::Range;
 use num::{abs, ToPrimitive};
 use plotters::coord::types::RangedCoordi32;
 use plotters::prelude::*;
@@ -18,16 +18,14 @@ pub struct Graph {
     node_list: HashMap<Point<usize>, (usize, Option<Point<usize>>)>,
     // for the visuals
     walls: HashSet<Point<usize>>,
-    xrange: Range<usize>,
-    yrange: Range<usize>,
+    bounds: Rect<usize>,
     start: Point<usize>,
     end: Point<usize>,
 }
 
 impl Graph {
     pub fn new(file: &str) -> Self {
-        let mut xrange = Range::default();
-        let mut yrange = Range::default();
+        let mut bounds = Rect::from_size(0, 0);
         let mut start = Point::default();
         let mut end = Point::default();
         let mut adjacency_list = HashMap::new();
@@ -40,8 +38,7 @@ impl Graph {
             .enumerate()
         {
             maze.push(row.chars().collect());
-            xrange = 0..row.len();
-            yrange = 0..y + 1;
+            bounds = Rect::from_size(row.len(), y + 1);
         }
         for (y, row) in maze.iter().enumerate() {
             for (x, c) in row.iter().enumerate() {
@@ -57,15 +54,12 @@ impl Graph {
                         }
                         let mut edges: Vec<Point<usize>> = Vec::new();
                         // let mut edges: Vec<(Point<usize>, EdgeData)> = Vec::new();
-                        for cardinal in (Point { x: x as i32, y: y as i32 }).cardinal_points() {
-                            let cardinal = Point { x: cardinal.x.to_usize().unwrap(), y: cardinal.y.to_usize().unwrap() };
-                            if xrange.contains(&cardinal.x) && yrange.contains(&cardinal.y) {
-                                let n = maze[cardinal.y][cardinal.x];
-                                match n {
-                                    '.' | 'S' | 'E' => edges.push(cardinal),
-                                    // '.' | 'S' | 'E' => edges.push((cardinal, EdgeData::Weight(1))),
-                                    _ => (),
-                                }
+                        for cardinal in (Point { x, y }).cardinal_within(bounds.width(), bounds.height()) {
+                            let n = maze[cardinal.y][cardinal.x];
+                            match n {
+                                '.' | 'S' | 'E' => edges.push(cardinal),
+                                // '.' | 'S' | 'E' => edges.push((cardinal, EdgeData::Weight(1))),
+                                _ => (),
                             }
                         }
                         let node = Point { x, y };
@@ -82,8 +76,7 @@ impl Graph {
             adjacency_list,
             node_list,
             walls,
-            xrange,
-            yrange,
+            bounds,
             start,
             end,
         }
@@ -0,0 +1,451 @@
+use crate::direction::ParseDirectionError;
+use crate::point::Point;
+use num::{Integer, Signed};
+use std::ops::{Add, Sub};
+use std::str::FromStr;
+
+// Hexagonal grids in axial coordinates, q and r, with s = -q - r the third
+// cube coordinate. Screen orientation, like Point: r grows downwards.
+// The same axial hexes serve both layouts, only the direction names and the
+// mapping onto a Point grid differ.
+#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq, Default, Ord, PartialOrd)]
+pub struct Hex<T> {
+    pub q: T,
+    pub r: T,
+}
+
+// Cube coordinates, always with q + r + s = 0
+#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq, Default, Ord, PartialOrd)]
+pub struct Cube<T> {
+    pub q: T,
+    pub r: T,
+    pub s: T,
+}
+
+// Pointy-top hexes sit in rows, so have neighbours E and W but not N and S.
+// Flat-top hexes sit in columns, so it's the other way round.
+#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq)]
+pub enum Layout {
+    PointyTop,
+    FlatTop,
+}
+
+// Pointy-top neighbours, clockwise from East
+#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq, Ord, PartialOrd)]
+pub enum PointyDirection {
+    East,
+    SouthEast,
+    SouthWest,
+    West,
+    NorthWest,
+    NorthEast,
+}
+
+// Flat-top neighbours, clockwise from North
+#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq, Ord, PartialOrd)]
+pub enum FlatDirection {
+    North,
+    NorthEast,
+    SouthEast,
+    South,
+    SouthWest,
+    NorthWest,
+}
+
+impl<T: Add<Output = T>> Add for Hex<T> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self::Output {
+        Self {
+            q: self.q + other.q,
+            r: self.r + other.r,
+        }
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Hex<T> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self::Output {
+        Self {
+            q: self.q - other.q,
+            r: self.r - other.r,
+        }
+    }
+}
+
+impl<T: Signed + Copy> From<Hex<T>> for Cube<T> {
+    fn from(h: Hex<T>) -> Self {
+        Cube {
+            q: h.q,
+            r: h.r,
+            s: -h.q - h.r,
+        }
+    }
+}
+
+impl<T> From<Cube<T>> for Hex<T> {
+    fn from(c: Cube<T>) -> Self {
+        Hex { q: c.q, r: c.r }
+    }
+}
+
+// The six axial offsets, clockwise from pointy-top East, which is flat-top
+// SouthEast
+fn offsets<T: Signed>() -> [Hex<T>; 6] {
+    let minus_one = || T::zero() - T::one();
+    let h = |q, r| Hex { q, r };
+    [
+        h(T::one(), T::zero()),
+        h(T::zero(), T::one()),
+        h(minus_one(), T::one()),
+        h(minus_one(), T::zero()),
+        h(T::zero(), minus_one()),
+        h(T::one(), minus_one()),
+    ]
+}
+
+impl<T: Signed + Copy + Ord> Hex<T> {
+    pub fn s(&self) -> T {
+        -self.q - self.r
+    }
+
+    // Steps between the two hexes
+    pub fn distance(&self, other: Self) -> T {
+        let d = Cube::from(*self - other);
+        d.q.abs().max(d.r.abs()).max(d.s.abs())
+    }
+
+    // The six neighbours, clockwise, the same for either layout
+    pub fn neighbours(&self) -> Vec<Self> {
+        offsets().iter().map(|&o| *self + o).collect()
+    }
+
+    // 60 degrees clockwise about the origin, as drawn with r down
+    pub fn rotate_right(&self) -> Self {
+        let c = Cube::from(*self);
+        Hex { q: -c.r, r: -c.s }
+    }
+
+    // 60 degrees anticlockwise about the origin
+    pub fn rotate_left(&self) -> Self {
+        let c = Cube::from(*self);
+        Hex { q: -c.s, r: -c.q }
+    }
+
+    pub fn rotate_right_about(&self, centre: &Self) -> Self {
+        (*self - *centre).rotate_right() + *centre
+    }
+
+    pub fn rotate_left_about(&self, centre: &Self) -> Self {
+        (*self - *centre).rotate_left() + *centre
+    }
+
+    // The 6 * radius hexes at exactly radius steps, clockwise. Just the hex
+    // itself for radius zero.
+    pub fn ring(&self, radius: T) -> Vec<Self> {
+        if radius.is_zero() {
+            return vec![*self];
+        }
+        let offsets = offsets();
+        // Start at the corner before East's, so walking each side in turn
+        // goes clockwise
+        let mut pos = *self + scale(offsets[4], radius);
+        let mut res = Vec::new();
+        for &o in &offsets {
+            let mut i = T::zero();
+            while i < radius {
+                res.push(pos);
+                pos = pos + o;
+                i = i + T::one();
+            }
+        }
+        res
+    }
+
+    // Every hex within radius steps, ring by ring outwards from the centre
+    pub fn spiral(&self, radius: T) -> Vec<Self> {
+        let mut res = Vec::new();
+        let mut k = T::zero();
+        while k <= radius {
+            res.extend(self.ring(k));
+            k = k + T::one();
+        }
+        res
+    }
+}
+
+fn scale<T: Signed + Copy>(h: Hex<T>, n: T) -> Hex<T> {
+    Hex {
+        q: h.q * n,
+        r: h.r * n,
+    }
+}
+
+// Hexes onto a Point grid in doubled coordinates, where every other column
+// (pointy-top) or row (flat-top) is skipped, so neighbours stay regular.
+// Pointy-top East is (2, 0), flat-top North is (0, -2).
+impl<T: Integer + Signed + Copy> Hex<T> {
+    pub fn to_point(&self, layout: Layout) -> Point<T> {
+        match layout {
+            Layout::PointyTop => Point {
+                x: self.q + self.q + self.r,
+                y: self.r,
+            },
+            Layout::FlatTop => Point {
+                x: self.q,
+                y: self.r + self.r + self.q,
+            },
+        }
+    }
+
+    // None for the points in between hexes, where x + y is odd
+    pub fn from_point(p: Point<T>, layout: Layout) -> Option<Self> {
+        let two = T::one() + T::one();
+        if !(p.x + p.y).is_multiple_of(&two) {
+            return None;
+        }
+        Some(match layout {
+            Layout::PointyTop => Hex {
+                q: (p.x - p.y) / two,
+                r: p.y,
+            },
+            Layout::FlatTop => Hex {
+                q: p.x,
+                r: (p.y - p.x) / two,
+            },
+        })
+    }
+}
+
+impl PointyDirection {
+    pub const ALL: [PointyDirection; 6] = [
+        PointyDirection::East,
+        PointyDirection::SouthEast,
+        PointyDirection::SouthWest,
+        PointyDirection::West,
+        PointyDirection::NorthWest,
+        PointyDirection::NorthEast,
+    ];
+
+    pub fn offset<T: Signed>(self) -> Hex<T> {
+        let [e, se, sw, w, nw, ne] = offsets();
+        match self {
+            PointyDirection::East => e,
+            PointyDirection::SouthEast => se,
+            PointyDirection::SouthWest => sw,
+            PointyDirection::West => w,
+            PointyDirection::NorthWest => nw,
+            PointyDirection::NorthEast => ne,
+        }
+    }
+
+    // Directions run together with no separator, eg. "nwwswee"
+    pub fn parse_path(s: &str) -> Result<Vec<PointyDirection>, ParseDirectionError> {
+        split_path(s).map(str::parse).collect()
+    }
+}
+
+impl FlatDirection {
+    pub const ALL: [FlatDirection; 6] = [
+        FlatDirection::North,
+        FlatDirection::NorthEast,
+        FlatDirection::SouthEast,
+        FlatDirection::South,
+        FlatDirection::SouthWest,
+        FlatDirection::NorthWest,
+    ];
+
+    pub fn offset<T: Signed>(self) -> Hex<T> {
+        let [se, s, sw, nw, n, ne] = offsets();
+        match self {
+            FlatDirection::North => n,
+            FlatDirection::NorthEast => ne,
+            FlatDirection::SouthEast => se,
+            FlatDirection::South => s,
+            FlatDirection::SouthWest => sw,
+            FlatDirection::NorthWest => nw,
+        }
+    }
+
+    // Directions run together with no separator, eg. "nenwsw". An 'n' or 's'
+    // followed by 'e' or 'w' is always read as one direction.
+    pub fn parse_path(s: &str) -> Result<Vec<FlatDirection>, ParseDirectionError> {
+        split_path(s).map(str::parse).collect()
+    }
+}
+
+// Cut a run of directions into its parts, taking an 'e' or 'w' after an 'n' or
+// 's' as part of the same direction
+fn split_path(s: &str) -> impl Iterator<Item = &str> {
+    let mut rest = s.trim();
+    std::iter::from_fn(move || {
+        let mut chars = rest.char_indices();
+        let (_, first) = chars.next()?;
+        let len = match chars.next() {
+            Some((i, c)) if matches!(first, 'n' | 's') && matches!(c, 'e' | 'w') => {
+                i + c.len_utf8()
+            }
+            Some((i, _)) => i,
+            None => rest.len(),
+        };
+        let (part, tail) = rest.split_at(len);
+        rest = tail;
+        Some(part)
+    })
+}
+
+impl FromStr for PointyDirection {
+    type Err = ParseDirectionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "e" => Ok(PointyDirection::East),
+            "se" => Ok(PointyDirection::SouthEast),
+            "sw" => Ok(PointyDirection::SouthWest),
+            "w" => Ok(PointyDirection::West),
+            "nw" => Ok(PointyDirection::NorthWest),
+            "ne" => Ok(PointyDirection::NorthEast),
+            _ => Err(ParseDirectionError(s.to_string())),
+        }
+    }
+}
+
+impl FromStr for FlatDirection {
+    type Err = ParseDirectionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "n" => Ok(FlatDirection::North),
+            "ne" => Ok(FlatDirection::NorthEast),
+            "se" => Ok(FlatDirection::SouthEast),
+            "s" => Ok(FlatDirection::South),
+            "sw" => Ok(FlatDirection::SouthWest),
+            "nw" => Ok(FlatDirection::NorthWest),
+            _ => Err(ParseDirectionError(s.to_string())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::hex::{Cube, FlatDirection, Hex, Layout, PointyDirection};
+    use crate::point::Point;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_flat_path() {
+        let walk = |s: &str| {
+            let end = s
+                .split(',')
+                .map(|d| d.parse::<FlatDirection>().unwrap())
+                .fold(Hex::<i32>::default(), |h, d| h + d.offset());
+            end.distance(Hex::default())
+        };
+        assert_eq!(walk("ne,ne,ne"), 3);
+        assert_eq!(walk("ne,ne,sw,sw"), 0);
+        assert_eq!(walk("ne,ne,s,s"), 2);
+        assert_eq!(walk("se,sw,se,sw,sw"), 3);
+        assert_eq!(
+            FlatDirection::parse_path("nenwsw"),
+            Ok(vec![
+                FlatDirection::NorthEast,
+                FlatDirection::NorthWest,
+                FlatDirection::SouthWest
+            ])
+        );
+        assert_eq!(
+            FlatDirection::parse_path("nsn"),
+            Ok(vec![
+                FlatDirection::North,
+                FlatDirection::South,
+                FlatDirection::North
+            ])
+        );
+        assert!(FlatDirection::parse_path("nex").is_err());
+    }
+
+    #[test]
+    fn test_pointy_path() {
+        let walk = |s: &str| -> Hex<i32> {
+            PointyDirection::parse_path(s)
+                .unwrap()
+                .iter()
+                .fold(Hex::default(), |h, d| h + d.offset())
+        };
+        assert_eq!(walk("nwwswee"), Hex::default());
+        assert_eq!(walk("esew"), PointyDirection::SouthEast.offset());
+        assert!(PointyDirection::parse_path("n").is_err());
+        // Opposite directions are three apart
+        for (i, d) in PointyDirection::ALL.iter().enumerate() {
+            let back = PointyDirection::ALL[(i + 3) % 6];
+            assert_eq!(d.offset::<i32>() + back.offset(), Hex::default());
+        }
+    }
+
+    #[test]
+    fn test_neighbours_and_distance() {
+        let h = Hex { q: 2, r: -1 };
+        let neighbours = h.neighbours();
+        assert_eq!(neighbours.len(), 6);
+        assert!(neighbours.iter().all(|n| n.distance(h) == 1));
+        let pointy: Vec<Hex<i32>> = PointyDirection::ALL
+            .iter()
+            .map(|d| h + d.offset())
+            .collect();
+        assert_eq!(pointy, neighbours);
+        let flat: HashSet<Hex<i32>> = FlatDirection::ALL.iter().map(|d| h + d.offset()).collect();
+        assert_eq!(flat, neighbours.iter().copied().collect());
+        assert_eq!(Hex { q: 0, r: 0 }.distance(Hex { q: 3, r: -5 }), 5);
+        let c = Cube::from(h);
+        assert_eq!((c.q, c.r, c.s), (2, -1, -1));
+        assert_eq!(Hex::from(c), h);
+    }
+
+    #[test]
+    fn test_rings() {
+        let centre = Hex { q: 1, r: 1 };
+        assert_eq!(centre.ring(0), vec![centre]);
+        for radius in 1..5 {
+            let ring = centre.ring(radius);
+            assert_eq!(ring.len() as i32, 6 * radius);
+            assert!(ring.iter().all(|h| h.distance(centre) == radius));
+            // Each hex is next to the one before
+            assert!(ring.windows(2).all(|w| w[0].distance(w[1]) == 1));
+        }
+        let spiral = centre.spiral(3);
+        assert_eq!(spiral.len(), 1 + 3 * 3 * 4);
+        assert_eq!(spiral.iter().collect::<HashSet<_>>().len(), spiral.len());
+    }
+
+    #[test]
+    fn test_rotation() {
+        let centre = Hex { q: 1, r: 0 };
+        let h = Hex { q: 3, r: -1 };
+        let mut p = h;
+        for _ in 0..6 {
+            let next = p.rotate_right_about(&centre);
+            assert_eq!(next.distance(centre), h.distance(centre));
+            assert_eq!(next.rotate_left_about(&centre), p);
+            p = next;
+        }
+        assert_eq!(p, h);
+        // East turns to South East, as drawn
+        let east: Hex<i32> = PointyDirection::East.offset();
+        assert_eq!(east.rotate_right(), PointyDirection::SouthEast.offset());
+    }
+
+    #[test]
+    fn test_points() {
+        for layout in [Layout::PointyTop, Layout::FlatTop] {
+            for h in Hex::<i32>::default().spiral(2) {
+                assert_eq!(Hex::from_point(h.to_point(layout), layout), Some(h));
+            }
+        }
+        let east: Hex<i32> = PointyDirection::East.offset();
+        assert_eq!(east.to_point(Layout::PointyTop), Point { x: 2, y: 0 });
+        let north: Hex<i32> = FlatDirection::North.offset();
+        assert_eq!(north.to_point(Layout::FlatTop), Point { x: 0, y: -2 });
+        assert_eq!(Hex::from_point(Point { x: 1, y: 0 }, Layout::FlatTop), None);
+    }
+}
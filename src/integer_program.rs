@@ -0,0 +1,225 @@
+use crate::divisors::lcm;
+use crate::linear_system::{row_reduce, Rational128};
+use num::rational::Ratio;
+use num::Zero;
+
+// Minimise x_0 + x_1 + ... subject to Ax = b and 0 <= x_j <= upper[j] for
+// integer x. Gaussian elimination leaves the pivot variables as functions of the
+// free ones, so only the free variables (usually very few) are searched, with
+// branches pruned once their partial sum can't beat the best found so far.
+pub fn min_sum_bounded(a: &[Vec<i64>], b: &[i64], upper: &[i64]) -> Option<Vec<i64>> {
+    assert_eq!(
+        a.len(),
+        b.len(),
+        "A and b must have the same number of rows"
+    );
+    let cols = upper.len();
+    let mut m: Vec<Vec<Rational128>> = a
+        .iter()
+        .zip(b)
+        .map(|(row, &rhs)| {
+            assert_eq!(row.len(), cols, "A must have one column per bound");
+            row.iter()
+                .chain([&rhs])
+                .map(|&v| Ratio::from_integer(v as i128))
+                .collect()
+        })
+        .collect();
+    let pivots = row_reduce(&mut m);
+    if m[pivots.len()..].iter().any(|row| !row[cols].is_zero()) {
+        return None;
+    }
+
+    // Scale each pivot row back to integers, d * x_p + sum(c_f * x_f) = e
+    let free: Vec<usize> = (0..cols).filter(|c| !pivots.contains(c)).collect();
+    let rows = pivots
+        .iter()
+        .zip(&m)
+        .map(|(&pivot, row)| {
+            let den = row.iter().fold(1, |acc, v| lcm(acc, *v.denom()));
+            let scale = |v: &Rational128| (v * den).to_integer();
+            PivotRow {
+                pivot,
+                den,
+                free: free.iter().map(|&f| scale(&row[f])).collect(),
+                rhs: scale(&row[cols]),
+            }
+        })
+        .collect();
+
+    let mut search = Search {
+        upper: upper.iter().map(|&u| u as i128).collect(),
+        free,
+        rows,
+        assigned: Vec::new(),
+        best: None,
+    };
+    search.dfs(0);
+    search
+        .best
+        .map(|(_, x)| x.into_iter().map(|v| v as i64).collect())
+}
+
+// As min_sum_bounded, with each of the cols variables bounded by the right hand
+// sides. Only valid when every entry of A is non-negative. The column count is
+// passed in as A may have no rows to take it from.
+pub fn min_sum(a: &[Vec<i64>], b: &[i64], cols: usize) -> Option<Vec<i64>> {
+    assert!(
+        a.iter().flatten().all(|&v| v >= 0),
+        "A must be non-negative"
+    );
+    let upper: Vec<i64> = (0..cols)
+        .map(|c| {
+            a.iter()
+                .zip(b)
+                .filter(|(row, _)| row[c] > 0)
+                .map(|(row, &rhs)| rhs / row[c])
+                .min()
+                .unwrap_or(0)
+        })
+        .collect();
+    min_sum_bounded(a, b, &upper)
+}
+
+// Fewest presses to bring every counter to its target, where buttons[j] lists
+// the counters that button j increments by one. Returns presses per button.
+pub fn min_presses(buttons: &[Vec<usize>], targets: &[i64]) -> Option<Vec<i64>> {
+    let a: Vec<Vec<i64>> = (0..targets.len())
+        .map(|counter| {
+            buttons
+                .iter()
+                .map(|button| button.iter().filter(|&&c| c == counter).count() as i64)
+                .collect()
+        })
+        .collect();
+    min_sum(&a, targets, buttons.len())
+}
+
+#[derive(Debug)]
+struct PivotRow {
+    pivot: usize,
+    den: i128,
+    free: Vec<i128>,
+    rhs: i128,
+}
+
+#[derive(Debug)]
+struct Search {
+    upper: Vec<i128>,
+    free: Vec<usize>,
+    rows: Vec<PivotRow>,
+    assigned: Vec<i128>,
+    best: Option<(i128, Vec<i128>)>,
+}
+
+impl Search {
+    fn dfs(&mut self, depth: usize) {
+        let partial: i128 = self.assigned.iter().sum();
+        if self.best.as_ref().is_some_and(|(best, _)| partial >= *best) {
+            return;
+        }
+        if depth == self.free.len() {
+            if let Some(x) = self.complete() {
+                let total = x.iter().sum();
+                if self.best.as_ref().is_none_or(|(best, _)| total < *best) {
+                    self.best = Some((total, x));
+                }
+            }
+            return;
+        }
+        for v in 0..=self.upper[self.free[depth]] {
+            self.assigned.push(v);
+            self.dfs(depth + 1);
+            self.assigned.pop();
+        }
+    }
+
+    // Fill in the pivot variables from the assigned free ones, None if any of
+    // them come out fractional or out of bounds
+    fn complete(&self) -> Option<Vec<i128>> {
+        let mut x = vec![0; self.upper.len()];
+        for (&f, &v) in self.free.iter().zip(&self.assigned) {
+            x[f] = v;
+        }
+        for row in &self.rows {
+            let num = row.rhs
+                - row
+                    .free
+                    .iter()
+                    .zip(&self.assigned)
+                    .map(|(c, v)| c * v)
+                    .sum::<i128>();
+            if num % row.den != 0 {
+                return None;
+            }
+            let value = num / row.den;
+            if value < 0 || value > self.upper[row.pivot] {
+                return None;
+            }
+            x[row.pivot] = value;
+        }
+        Some(x)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_min_presses() {
+        // (3) (1,3) (2) (2,3) (0,2) (0,1) {3,5,4,7}
+        let buttons = vec![
+            vec![3],
+            vec![1, 3],
+            vec![2],
+            vec![2, 3],
+            vec![0, 2],
+            vec![0, 1],
+        ];
+        let presses = min_presses(&buttons, &[3, 5, 4, 7]).unwrap();
+        assert_eq!(presses.iter().sum::<i64>(), 10);
+
+        // (0,2,3,4) (2,3) (0,4) (0,1,2) (1,2,3,4) {7,5,12,7,2}
+        let buttons = vec![
+            vec![0, 2, 3, 4],
+            vec![2, 3],
+            vec![0, 4],
+            vec![0, 1, 2],
+            vec![1, 2, 3, 4],
+        ];
+        let presses = min_presses(&buttons, &[7, 5, 12, 7, 2]).unwrap();
+        assert_eq!(presses.iter().sum::<i64>(), 12);
+
+        // (0,1,2,3,4) (0,3,4) (0,1,2,4,5) (1,2) {10,11,11,5,10,5}
+        let buttons = vec![
+            vec![0, 1, 2, 3, 4],
+            vec![0, 3, 4],
+            vec![0, 1, 2, 4, 5],
+            vec![1, 2],
+        ];
+        let presses = min_presses(&buttons, &[10, 11, 11, 5, 10, 5]).unwrap();
+        assert_eq!(presses.iter().sum::<i64>(), 11);
+
+        // No counters, so nothing needs pressing
+        assert_eq!(min_presses(&[vec![0], vec![1]], &[]), Some(vec![0, 0]));
+    }
+
+    #[test]
+    fn test_infeasible() {
+        // Both counters always move together
+        assert_eq!(min_presses(&[vec![0, 1]], &[1, 2]), None);
+        // 2x = 3 has no integer solution
+        assert_eq!(min_sum(&[vec![2]], &[3], 1), None);
+    }
+
+    #[test]
+    fn test_min_sum_bounded() {
+        // x - y = 2 with x, y <= 5: smallest is x = 2, y = 0
+        assert_eq!(
+            min_sum_bounded(&[vec![1, -1]], &[2], &[5, 5]),
+            Some(vec![2, 0])
+        );
+        assert_eq!(min_sum_bounded(&[vec![1, -1]], &[2], &[1, 5]), None);
+    }
+}
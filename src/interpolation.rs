@@ -0,0 +1,185 @@
+use crate::linear_system::Rational128;
+use num::rational::Ratio;
+use num::Zero;
+
+// The difference triangle of a sequence, stopping at the first row that is all
+// zeros (not included). The first row is the sequence itself.
+pub fn differences(seq: &[i128]) -> Vec<Vec<i128>> {
+    let mut triangle = Vec::new();
+    let mut row = seq.to_vec();
+    while row.iter().any(|&v| v != 0) {
+        let next = row.windows(2).map(|w| w[1] - w[0]).collect();
+        triangle.push(row);
+        row = next;
+    }
+    triangle
+}
+
+// Value at index x of the minimal-degree polynomial through seq[0], seq[1], ...
+// using Newton's forward difference formula
+//      f(x) = sum over k of  (delta^k f)(0) * C(x, k)
+// x can be anywhere, including before the start (negative) or far beyond it.
+// The result is exact and always an integer.
+pub fn extrapolate(seq: &[i128], x: i128) -> i128 {
+    let mut total = 0;
+    let mut binomial = 1;
+    for (k, row) in differences(seq).iter().enumerate() {
+        if k > 0 {
+            // C(x, k) = C(x, k - 1) * (x - k + 1) / k, always exact
+            binomial = binomial * (x - k as i128 + 1) / k as i128;
+        }
+        total += row[0] * binomial;
+    }
+    total
+}
+
+pub fn next_value(seq: &[i128]) -> i128 {
+    extrapolate(seq, seq.len() as i128)
+}
+
+pub fn previous_value(seq: &[i128]) -> i128 {
+    extrapolate(seq, -1)
+}
+
+// Coefficients in ascending powers, without trailing zeros
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Polynomial {
+    coeffs: Vec<Rational128>,
+}
+
+impl Polynomial {
+    pub fn new(mut coeffs: Vec<Rational128>) -> Polynomial {
+        while coeffs.last().is_some_and(|c| c.is_zero()) {
+            coeffs.pop();
+        }
+        Polynomial { coeffs }
+    }
+
+    // The unique minimal-degree polynomial through the given points, by Newton's
+    // divided differences. The x values must be distinct.
+    pub fn interpolate(points: &[(Rational128, Rational128)]) -> Polynomial {
+        let xs: Vec<Rational128> = points.iter().map(|p| p.0).collect();
+        let mut table: Vec<Rational128> = points.iter().map(|p| p.1).collect();
+        // After pass k, table[i] holds f[x_(i-k), ..., x_i]
+        for k in 1..xs.len() {
+            for i in (k..xs.len()).rev() {
+                let dx = xs[i] - xs[i - k];
+                assert!(!dx.is_zero(), "x values must be distinct");
+                table[i] = (table[i] - table[i - 1]) / dx;
+            }
+        }
+        // Expand the Newton form from the innermost bracket outwards
+        let mut coeffs: Vec<Rational128> = Vec::new();
+        for i in (0..xs.len()).rev() {
+            // coeffs = coeffs * (x - x_i) + table[i]
+            let mut next = vec![Rational128::zero(); coeffs.len() + 1];
+            for (j, c) in coeffs.iter().enumerate() {
+                next[j + 1] += c;
+                next[j] -= c * xs[i];
+            }
+            next[0] += table[i];
+            coeffs = next;
+        }
+        Polynomial::new(coeffs)
+    }
+
+    // Points at x = 0, 1, 2, ...
+    pub fn from_sequence(seq: &[i128]) -> Polynomial {
+        let points: Vec<(Rational128, Rational128)> = seq
+            .iter()
+            .enumerate()
+            .map(|(x, &y)| (Ratio::from_integer(x as i128), Ratio::from_integer(y)))
+            .collect();
+        Polynomial::interpolate(&points)
+    }
+
+    pub fn coefficients(&self) -> &[Rational128] {
+        &self.coeffs
+    }
+
+    // None for the zero polynomial
+    pub fn degree(&self) -> Option<usize> {
+        self.coeffs.len().checked_sub(1)
+    }
+
+    // Horner's method
+    pub fn eval(&self, x: Rational128) -> Rational128 {
+        self.coeffs
+            .iter()
+            .rev()
+            .fold(Rational128::zero(), |acc, c| acc * x + c)
+    }
+
+    pub fn eval_integer(&self, x: i128) -> Rational128 {
+        self.eval(Ratio::from_integer(x))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn r(n: i128) -> Rational128 {
+        Ratio::from_integer(n)
+    }
+
+    #[test]
+    fn test_differences() {
+        assert_eq!(
+            differences(&[1, 3, 6, 10, 15, 21]),
+            vec![
+                vec![1, 3, 6, 10, 15, 21],
+                vec![2, 3, 4, 5, 6],
+                vec![1, 1, 1, 1],
+            ]
+        );
+        assert!(differences(&[0, 0, 0]).is_empty());
+    }
+
+    #[test]
+    fn test_next_and_previous() {
+        assert_eq!(next_value(&[0, 3, 6, 9, 12, 15]), 18);
+        assert_eq!(next_value(&[1, 3, 6, 10, 15, 21]), 28);
+        assert_eq!(next_value(&[10, 13, 16, 21, 30, 45]), 68);
+        assert_eq!(previous_value(&[0, 3, 6, 9, 12, 15]), -3);
+        assert_eq!(previous_value(&[1, 3, 6, 10, 15, 21]), 0);
+        assert_eq!(previous_value(&[10, 13, 16, 21, 30, 45]), 5);
+    }
+
+    #[test]
+    fn test_extrapolate_far() {
+        // Squares, far beyond the samples and before them
+        assert_eq!(extrapolate(&[0, 1, 4], 202300), 202300 * 202300);
+        assert_eq!(extrapolate(&[0, 1, 4], -7), 49);
+    }
+
+    #[test]
+    fn test_polynomial_from_sequence() {
+        let p = Polynomial::from_sequence(&[1, 3, 6, 10, 15]);
+        // (x + 1)(x + 2) / 2
+        assert_eq!(p.degree(), Some(2));
+        assert_eq!(
+            p.coefficients(),
+            &[r(1), Ratio::new(3, 2), Ratio::new(1, 2)]
+        );
+        assert_eq!(p.eval_integer(5), r(21));
+        assert_eq!(Polynomial::from_sequence(&[0, 0]).degree(), None);
+    }
+
+    #[test]
+    fn test_interpolate() {
+        // Quadratic growth sampled at 65, 65 + 131, 65 + 2 * 131
+        let points = [(r(65), r(3884)), (r(196), r(34564)), (r(327), r(95816))];
+        let p = Polynomial::interpolate(&points);
+        assert_eq!(p.degree(), Some(2));
+        for (x, y) in points {
+            assert_eq!(p.eval(x), y);
+        }
+        // Agrees with the forward difference form on evenly spaced samples
+        let n = 202300;
+        assert_eq!(
+            p.eval_integer(65 + 131 * n),
+            r(extrapolate(&[3884, 34564, 95816], n))
+        );
+    }
+}
@@ -0,0 +1,348 @@
+use crate::point::Point;
+use crate::point3::Point3;
+use num::{BigInt, BigRational, Signed, Zero};
+use std::cmp::Ordering;
+
+// Exact intersections of lines and segments with integer coordinates. Sides
+// are decided with i128 cross products, which can't overflow for coordinates
+// below 2^62, and any point that isn't on the lattice comes back as a
+// BigRational, so nothing goes wrong at the 10^14 magnitudes floats struggle
+// with.
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SegmentIntersection {
+    Disjoint,
+    // The segments cross at a point inside both
+    Proper(Point<BigRational>),
+    // They meet at a single point that is an end of at least one of them
+    Touching(Point<i64>),
+    // Collinear and sharing the part from one point to the other
+    Overlap(Point<i64>, Point<i64>),
+}
+
+pub(crate) fn wide(p: Point<i64>) -> Point<i128> {
+    p.cast()
+}
+
+// Which side of the line through a and b that c is on, zero if on the line
+pub(crate) fn orientation(a: Point<i64>, b: Point<i64>, c: Point<i64>) -> i128 {
+    (wide(b) - wide(a)).cross(wide(c) - wide(a)).signum()
+}
+
+// c lies within the bounding box of a and b, so on the segment if collinear
+pub(crate) fn within(a: Point<i64>, b: Point<i64>, c: Point<i64>) -> bool {
+    a.x.min(b.x) <= c.x && c.x <= a.x.max(b.x) && a.y.min(b.y) <= c.y && c.y <= a.y.max(b.y)
+}
+
+fn rational(n: i128) -> BigRational {
+    BigRational::from_integer(BigInt::from(n))
+}
+
+// How segment ab meets segment cd, ends included
+pub fn segment_intersection(
+    a: Point<i64>,
+    b: Point<i64>,
+    c: Point<i64>,
+    d: Point<i64>,
+) -> SegmentIntersection {
+    let (o1, o2) = (orientation(a, b, c), orientation(a, b, d));
+    let (o3, o4) = (orientation(c, d, a), orientation(c, d, b));
+
+    if o1 == 0 && o2 == 0 && o3 == 0 && o4 == 0 {
+        // All on one line, where ordering by x then y is ordering along it
+        let lo = a.min(b).max(c.min(d));
+        let hi = a.max(b).min(c.max(d));
+        return match lo.cmp(&hi) {
+            Ordering::Greater => SegmentIntersection::Disjoint,
+            Ordering::Equal => SegmentIntersection::Touching(lo),
+            Ordering::Less => SegmentIntersection::Overlap(lo, hi),
+        };
+    }
+
+    if o1 * o2 < 0 && o3 * o4 < 0 {
+        let (r, s) = (wide(b) - wide(a), wide(d) - wide(c));
+        let num = (wide(c) - wide(a)).cross(s);
+        let den = r.cross(s);
+        let t = BigRational::new(BigInt::from(num), BigInt::from(den));
+        return SegmentIntersection::Proper(Point {
+            x: rational(a.x.into()) + rational(r.x) * &t,
+            y: rational(a.y.into()) + rational(r.y) * &t,
+        });
+    }
+
+    [(o1, a, b, c), (o2, a, b, d), (o3, c, d, a), (o4, c, d, b)]
+        .into_iter()
+        .find(|&(o, p, q, end)| o == 0 && within(p, q, end))
+        .map_or(SegmentIntersection::Disjoint, |(_, _, _, end)| {
+            SegmentIntersection::Touching(end)
+        })
+}
+
+pub fn segments_intersect(a: Point<i64>, b: Point<i64>, c: Point<i64>, d: Point<i64>) -> bool {
+    segment_intersection(a, b, c, d) != SegmentIntersection::Disjoint
+}
+
+// For the lines p + t * r and q + u * s, the t and u where they cross. None if
+// they're parallel, including when they're the same line.
+pub fn line_parameters(
+    p: Point<i64>,
+    r: Point<i64>,
+    q: Point<i64>,
+    s: Point<i64>,
+) -> Option<(BigRational, BigRational)> {
+    let (r, s) = (wide(r), wide(s));
+    let den = r.cross(s);
+    if den == 0 {
+        return None;
+    }
+    let qp = wide(q) - wide(p);
+    let den = BigInt::from(den);
+    Some((
+        BigRational::new(BigInt::from(qp.cross(s)), den.clone()),
+        BigRational::new(BigInt::from(qp.cross(r)), den),
+    ))
+}
+
+// Where the lines p + t * r and q + u * s cross, None if parallel. Eg. the
+// future paths of two hailstones, check line_parameters for which way along
+// each path it is.
+pub fn line_intersection(
+    p: Point<i64>,
+    r: Point<i64>,
+    q: Point<i64>,
+    s: Point<i64>,
+) -> Option<Point<BigRational>> {
+    let (t, _) = line_parameters(p, r, q, s)?;
+    Some(Point {
+        x: rational(p.x.into()) + rational(r.x.into()) * &t,
+        y: rational(p.y.into()) + rational(r.y.into()) * &t,
+    })
+}
+
+// Closest approach of the rays p1 + t * d1 and p2 + u * d2, with t, u >= 0
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClosestApproach {
+    pub t: BigRational,
+    pub u: BigRational,
+    pub squared_distance: BigRational,
+}
+
+impl ClosestApproach {
+    pub fn intersects(&self) -> bool {
+        self.squared_distance.is_zero()
+    }
+}
+
+fn dot3(a: Point3<i64>, b: Point3<i64>) -> BigRational {
+    let d = |x: i64, y: i64| BigInt::from(x) * BigInt::from(y);
+    BigRational::from_integer(d(a.x, b.x) + d(a.y, b.y) + d(a.z, b.z))
+}
+
+pub fn ray_closest_approach(
+    p1: Point3<i64>,
+    d1: Point3<i64>,
+    p2: Point3<i64>,
+    d2: Point3<i64>,
+) -> ClosestApproach {
+    // |w + t * d1 - u * d2|^2 = ww + t^2 a + u^2 c + 2t d - 2u e - 2tu b
+    let w = p1 - p2;
+    let (a, b, c) = (dot3(d1, d1), dot3(d1, d2), dot3(d2, d2));
+    let (d, e, ww) = (dot3(d1, w), dot3(d2, w), dot3(w, w));
+    let two = rational(2);
+    let squared_distance = |t: &BigRational, u: &BigRational| {
+        &ww + t * t * &a + u * u * &c + &two * t * &d - &two * u * &e - &two * t * u * &b
+    };
+    let approach = |t: BigRational, u: BigRational| ClosestApproach {
+        squared_distance: squared_distance(&t, &u),
+        t,
+        u,
+    };
+
+    // The unconstrained minimum, if the lines aren't parallel and it's ahead
+    // on both rays
+    let denom = &a * &c - &b * &b;
+    if !denom.is_zero() {
+        let t = (&b * &e - &c * &d) / &denom;
+        let u = (&a * &e - &b * &d) / &denom;
+        if !t.is_negative() && !u.is_negative() {
+            return approach(t, u);
+        }
+    }
+    // Otherwise it's on the edge of t, u >= 0, with one ray at its start
+    let zero = BigRational::zero();
+    let u = if c.is_zero() {
+        zero.clone()
+    } else {
+        (&e / &c).max(zero.clone())
+    };
+    let t = if a.is_zero() {
+        zero.clone()
+    } else {
+        (-&d / &a).max(zero.clone())
+    };
+    let from_p1 = approach(zero.clone(), u);
+    let from_p2 = approach(t, zero);
+    if from_p1.squared_distance <= from_p2.squared_distance {
+        from_p1
+    } else {
+        from_p2
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::intersection::{
+        line_intersection, line_parameters, ray_closest_approach, segment_intersection,
+        segments_intersect, SegmentIntersection,
+    };
+    use crate::point::Point;
+    use crate::point3::Point3;
+    use num::{BigInt, BigRational};
+
+    fn r(n: i64, d: i64) -> BigRational {
+        BigRational::new(BigInt::from(n), BigInt::from(d))
+    }
+
+    #[test]
+    fn test_segment_intersection() {
+        let p = |x, y| Point { x, y };
+        assert_eq!(
+            segment_intersection(p(0, 0), p(4, 4), p(0, 4), p(4, 0)),
+            SegmentIntersection::Proper(Point {
+                x: r(2, 1),
+                y: r(2, 1)
+            })
+        );
+        assert_eq!(
+            segment_intersection(p(0, 0), p(3, 1), p(0, 1), p(3, 0)),
+            SegmentIntersection::Proper(Point {
+                x: r(3, 2),
+                y: r(1, 2)
+            })
+        );
+        // T junction and shared end
+        assert_eq!(
+            segment_intersection(p(0, 0), p(4, 0), p(2, 0), p(2, 5)),
+            SegmentIntersection::Touching(p(2, 0))
+        );
+        assert_eq!(
+            segment_intersection(p(0, 0), p(4, 0), p(4, 0), p(4, 5)),
+            SegmentIntersection::Touching(p(4, 0))
+        );
+        // Collinear
+        assert_eq!(
+            segment_intersection(p(0, 0), p(6, 3), p(8, 4), p(2, 1)),
+            SegmentIntersection::Overlap(p(2, 1), p(6, 3))
+        );
+        assert_eq!(
+            segment_intersection(p(0, 0), p(2, 0), p(2, 0), p(5, 0)),
+            SegmentIntersection::Touching(p(2, 0))
+        );
+        assert!(!segments_intersect(p(0, 0), p(2, 0), p(3, 0), p(5, 0)));
+        assert!(!segments_intersect(p(0, 0), p(2, 2), p(3, 0), p(3, 5)));
+        // Parallel but not collinear
+        assert!(!segments_intersect(p(0, 0), p(2, 0), p(0, 1), p(2, 1)));
+    }
+
+    #[test]
+    fn test_hailstones() {
+        let input = "19, 13, 30 @ -2,  1, -2
+18, 19, 22 @ -1, -1, -2
+20, 25, 34 @ -2, -2, -4
+12, 31, 28 @ -1, -2, -1
+20, 19, 15 @  1, -5, -3";
+        let stones: Vec<(Point<i64>, Point<i64>)> = input
+            .lines()
+            .map(|line| {
+                let v: Vec<i64> = line
+                    .split([',', '@'])
+                    .map(|s| s.trim().parse().unwrap())
+                    .collect();
+                (Point { x: v[0], y: v[1] }, Point { x: v[3], y: v[4] })
+            })
+            .collect();
+        let (lo, hi) = (r(7, 1), r(27, 1));
+        let mut count = 0;
+        for (i, &(p, v)) in stones.iter().enumerate() {
+            for &(q, w) in &stones[i + 1..] {
+                if let Some((t, u)) = line_parameters(p, v, q, w) {
+                    let x = line_intersection(p, v, q, w).unwrap();
+                    let inside = lo <= x.x && x.x <= hi && lo <= x.y && x.y <= hi;
+                    if t >= r(0, 1) && u >= r(0, 1) && inside {
+                        count += 1;
+                    }
+                }
+            }
+        }
+        assert_eq!(count, 2);
+        // The first pair cross at (14.333, 15.333)
+        let (p, v) = stones[0];
+        let (q, w) = stones[1];
+        assert_eq!(
+            line_intersection(p, v, q, w),
+            Some(Point {
+                x: r(43, 3),
+                y: r(46, 3)
+            })
+        );
+    }
+
+    #[test]
+    fn test_large_coordinates() {
+        // Near enough parallel that f64 puts the crossing in the wrong place
+        let p = Point { x: 0, y: 0 };
+        let v = Point {
+            x: 100_000_000_000_000,
+            y: 100_000_000_000_001,
+        };
+        let q = Point { x: 1, y: 0 };
+        let w = Point {
+            x: 100_000_000_000_000,
+            y: 100_000_000_000_000,
+        };
+        let x = line_intersection(p, v, q, w).unwrap();
+        assert_eq!(x.x, r(-100_000_000_000_000, 1));
+        assert_eq!(x.y, r(-100_000_000_000_001, 1));
+    }
+
+    #[test]
+    fn test_ray_closest_approach() {
+        let o = Point3 { x: 0, y: 0, z: 0 };
+        // Crossing rays
+        let c = ray_closest_approach(
+            o,
+            Point3 { x: 1, y: 1, z: 0 },
+            Point3 { x: 4, y: 0, z: 0 },
+            Point3 { x: -1, y: 1, z: 0 },
+        );
+        assert!(c.intersects());
+        assert_eq!((c.t, c.u), (r(2, 1), r(2, 1)));
+        // Skew lines, one unit apart in z
+        let c = ray_closest_approach(
+            o,
+            Point3 { x: 1, y: 0, z: 0 },
+            Point3 { x: 3, y: -5, z: 1 },
+            Point3 { x: 0, y: 1, z: 0 },
+        );
+        assert_eq!(c.squared_distance, r(1, 1));
+        assert_eq!((c.t, c.u), (r(3, 1), r(5, 1)));
+        // The lines cross behind the second ray, so it's closest at its start
+        let c = ray_closest_approach(
+            o,
+            Point3 { x: 1, y: 0, z: 0 },
+            Point3 { x: 3, y: 2, z: 0 },
+            Point3 { x: 0, y: 1, z: 0 },
+        );
+        assert!(!c.intersects());
+        assert_eq!((c.t, c.u), (r(3, 1), r(0, 1)));
+        assert_eq!(c.squared_distance, r(4, 1));
+        // Parallel
+        let c = ray_closest_approach(
+            o,
+            Point3 { x: 0, y: 0, z: 2 },
+            Point3 { x: 0, y: 3, z: -5 },
+            Point3 { x: 0, y: 0, z: 1 },
+        );
+        assert_eq!(c.squared_distance, r(9, 1));
+    }
+}
@@ -0,0 +1,342 @@
+use num::Zero;
+use std::ops::{Add, Range, Sub};
+
+// A set of values kept as sorted, disjoint, half-open ranges. Ranges that
+// overlap or touch are merged as they go in, so 1..3 and 3..5 become 1..5.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IntervalSet<T> {
+    ranges: Vec<Range<T>>,
+}
+
+impl<T> IntervalSet<T>
+where
+    T: Copy + Ord + Zero + Sub<Output = T>,
+{
+    pub fn new() -> IntervalSet<T> {
+        IntervalSet { ranges: Vec::new() }
+    }
+
+    pub fn ranges(&self) -> &[Range<T>] {
+        &self.ranges
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    // Total number of values covered
+    pub fn covered_len(&self) -> T {
+        self.ranges
+            .iter()
+            .fold(T::zero(), |sum, r| sum + (r.end - r.start))
+    }
+
+    pub fn contains(&self, value: &T) -> bool {
+        let i = self.ranges.partition_point(|r| r.end <= *value);
+        self.ranges.get(i).is_some_and(|r| r.start <= *value)
+    }
+
+    // Every value of range is in the set, true for an empty range
+    pub fn contains_range(&self, range: &Range<T>) -> bool {
+        if range.is_empty() {
+            return true;
+        }
+        let i = self.ranges.partition_point(|r| r.end < range.end);
+        self.ranges.get(i).is_some_and(|r| r.start <= range.start)
+    }
+
+    pub fn insert(&mut self, range: Range<T>) {
+        if range.is_empty() {
+            return;
+        }
+        // Everything from i to j overlaps or touches the new range
+        let i = self.ranges.partition_point(|r| r.end < range.start);
+        let j = self.ranges.partition_point(|r| r.start <= range.end);
+        let merged = if i < j {
+            range.start.min(self.ranges[i].start)..range.end.max(self.ranges[j - 1].end)
+        } else {
+            range
+        };
+        self.ranges.splice(i..j, [merged]);
+    }
+
+    pub fn remove(&mut self, range: Range<T>) {
+        if range.is_empty() {
+            return;
+        }
+        // Everything from i to j overlaps the range being removed
+        let i = self.ranges.partition_point(|r| r.end <= range.start);
+        let j = self.ranges.partition_point(|r| r.start < range.end);
+        if i == j {
+            return;
+        }
+        let mut rest = Vec::new();
+        if self.ranges[i].start < range.start {
+            rest.push(self.ranges[i].start..range.start);
+        }
+        if self.ranges[j - 1].end > range.end {
+            rest.push(range.end..self.ranges[j - 1].end);
+        }
+        self.ranges.splice(i..j, rest);
+    }
+
+    pub fn union(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+        let mut res = self.clone();
+        for r in &other.ranges {
+            res.insert(r.clone());
+        }
+        res
+    }
+
+    pub fn intersection(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+        let mut ranges = Vec::new();
+        let (mut i, mut j) = (0, 0);
+        while i < self.ranges.len() && j < other.ranges.len() {
+            let (a, b) = (&self.ranges[i], &other.ranges[j]);
+            let overlap = a.start.max(b.start)..a.end.min(b.end);
+            if !overlap.is_empty() {
+                ranges.push(overlap);
+            }
+            if a.end < b.end {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+        IntervalSet { ranges }
+    }
+
+    // Everything in bounds that isn't in the set
+    pub fn complement(&self, bounds: Range<T>) -> IntervalSet<T> {
+        let mut res = IntervalSet::new();
+        res.insert(bounds);
+        for r in &self.ranges {
+            res.remove(r.clone());
+        }
+        res
+    }
+}
+
+impl<T> FromIterator<Range<T>> for IntervalSet<T>
+where
+    T: Copy + Ord + Zero + Sub<Output = T>,
+{
+    fn from_iter<I: IntoIterator<Item = Range<T>>>(iter: I) -> Self {
+        let mut res = IntervalSet::new();
+        for r in iter {
+            res.insert(r);
+        }
+        res
+    }
+}
+
+// Piecewise offsets: a value in one of the source ranges moves by that range's
+// offset, anything else maps to itself. Eg. one seed-to-soil style almanac
+// map, where "50 98 2" sends 98..100 to 50..52.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RangeMap<T> {
+    // Sorted by start, not overlapping, each with where its start goes. Kept as
+    // a destination rather than an offset so unsigned T never goes negative.
+    pieces: Vec<(Range<T>, T)>,
+}
+
+impl<T> RangeMap<T>
+where
+    T: Copy + Ord + Zero + Add<Output = T> + Sub<Output = T>,
+{
+    pub fn new() -> RangeMap<T> {
+        RangeMap { pieces: Vec::new() }
+    }
+
+    // Send source to the range starting at destination. Panics if source
+    // overlaps a range already in the map.
+    pub fn insert(&mut self, source: Range<T>, destination: T) {
+        if source.is_empty() {
+            return;
+        }
+        let i = self.pieces.partition_point(|(r, _)| r.end <= source.start);
+        assert!(
+            self.pieces
+                .get(i)
+                .is_none_or(|(r, _)| r.start >= source.end),
+            "source ranges must not overlap"
+        );
+        self.pieces.insert(i, (source, destination));
+    }
+
+    pub fn map(&self, value: T) -> T {
+        let i = self.pieces.partition_point(|(r, _)| r.end <= value);
+        match self.pieces.get(i) {
+            Some((r, dest)) if r.start <= value => *dest + (value - r.start),
+            _ => value,
+        }
+    }
+
+    // Where every value of range ends up, as one range per piece it's split
+    // into, in the order of the source values
+    pub fn map_range(&self, range: Range<T>) -> Vec<Range<T>> {
+        let mut res = Vec::new();
+        let mut start = range.start;
+        let i = self.pieces.partition_point(|(r, _)| r.end <= start);
+        for (r, dest) in &self.pieces[i..] {
+            if start >= range.end || r.start >= range.end {
+                break;
+            }
+            // Unmapped gap before this piece
+            if start < r.start {
+                res.push(start..r.start);
+                start = r.start;
+            }
+            let end = r.end.min(range.end);
+            res.push(*dest + (start - r.start)..*dest + (end - r.start));
+            start = end;
+        }
+        if start < range.end {
+            res.push(start..range.end);
+        }
+        res
+    }
+
+    // Where the whole set ends up
+    pub fn map_set(&self, set: &IntervalSet<T>) -> IntervalSet<T> {
+        set.ranges()
+            .iter()
+            .flat_map(|r| self.map_range(r.clone()))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::interval_set::{IntervalSet, RangeMap};
+
+    #[test]
+    fn test_insert_and_remove() {
+        let mut set: IntervalSet<i64> = [5..8, 1..3, 3..4, 10..12].into_iter().collect();
+        assert_eq!(set.ranges(), &[1..4, 5..8, 10..12]);
+        assert_eq!(set.covered_len(), 8);
+        set.insert(6..11);
+        assert_eq!(set.ranges(), &[1..4, 5..12]);
+        set.insert(0..0);
+        assert_eq!(set.ranges().len(), 2);
+        set.remove(2..6);
+        assert_eq!(set.ranges(), &[1..2, 6..12]);
+        set.remove(7..8);
+        assert_eq!(set.ranges(), &[1..2, 6..7, 8..12]);
+        set.remove(0..20);
+        assert!(set.is_empty());
+    }
+
+    #[test]
+    fn test_queries() {
+        let set: IntervalSet<i64> = [3..6, 10..15, 16..21, 12..19].into_iter().collect();
+        assert_eq!(set.ranges(), &[3..6, 10..21]);
+        assert!(set.contains(&5) && set.contains(&17));
+        assert!(!set.contains(&1) && !set.contains(&6) && !set.contains(&21));
+        assert!(set.contains_range(&(11..21)));
+        assert!(!set.contains_range(&(5..11)));
+        assert!(set.contains_range(&(30..30)));
+
+        let other: IntervalSet<i64> = [0..4, 5..12, 20..30].into_iter().collect();
+        assert_eq!(
+            set.intersection(&other).ranges(),
+            &[3..4, 5..6, 10..12, 20..21]
+        );
+        let union = set.union(&other);
+        assert_eq!(
+            (union.ranges().len(), union.ranges()[0].clone()),
+            (1, 0..30)
+        );
+        assert_eq!(set.complement(0..25).ranges(), &[0..3, 6..10, 21..25]);
+        assert_eq!(set.complement(4..5).ranges(), &[]);
+    }
+
+    const ALMANAC: &str = "seeds: 79 14 55 13
+
+seed-to-soil map:
+50 98 2
+52 50 48
+
+soil-to-fertilizer map:
+0 15 37
+37 52 2
+39 0 15
+
+fertilizer-to-water map:
+49 53 8
+0 11 42
+42 0 7
+57 7 4
+
+water-to-light map:
+88 18 7
+18 25 70
+
+light-to-temperature map:
+45 77 23
+81 45 19
+68 64 13
+
+temperature-to-humidity map:
+0 69 1
+1 0 69
+
+humidity-to-location map:
+60 56 37
+56 93 4";
+
+    #[test]
+    fn test_almanac() {
+        let mut sections = ALMANAC.split("\n\n");
+        let seeds: Vec<i64> = sections
+            .next()
+            .unwrap()
+            .split_whitespace()
+            .skip(1)
+            .map(|s| s.parse().unwrap())
+            .collect();
+        let maps: Vec<RangeMap<i64>> = sections
+            .map(|section| {
+                let mut map = RangeMap::new();
+                for line in section.lines().skip(1) {
+                    let v: Vec<i64> = line
+                        .split_whitespace()
+                        .map(|s| s.parse().unwrap())
+                        .collect();
+                    map.insert(v[1]..v[1] + v[2], v[0]);
+                }
+                map
+            })
+            .collect();
+
+        let lowest = seeds
+            .iter()
+            .map(|&s| maps.iter().fold(s, |v, m| m.map(v)))
+            .min();
+        assert_eq!(lowest, Some(35));
+
+        let seed_ranges: IntervalSet<i64> = seeds.chunks(2).map(|c| c[0]..c[0] + c[1]).collect();
+        let locations = maps.iter().fold(seed_ranges, |set, m| m.map_set(&set));
+        assert_eq!(locations.ranges()[0].start, 46);
+        assert_eq!(locations.covered_len(), 27);
+    }
+
+    #[test]
+    fn test_map_range() {
+        let mut map = RangeMap::new();
+        map.insert(10..20, 100);
+        map.insert(30..35, 0);
+        assert_eq!(map.map(15), 105);
+        assert_eq!(map.map(25), 25);
+        assert_eq!(map.map(30), 0);
+        assert_eq!(map.map_range(5..32), vec![5..10, 100..110, 20..30, 0..2]);
+        assert_eq!(map.map_range(12..14), vec![102..104]);
+        assert_eq!(map.map_range(40..50), vec![40..50]);
+
+        // Unsigned, mapping down below the source
+        let mut map: RangeMap<u64> = RangeMap::new();
+        map.insert(10..20, 2);
+        assert_eq!(map.map(15), 7);
+        assert_eq!(map.map_range(8..12), vec![8..10, 2..4]);
+    }
+}
@@ -0,0 +1,354 @@
+use crate::point::Point;
+use crate::point3::Point3;
+use crate::point_n::PointN;
+use num::Zero;
+use std::cmp::Ordering;
+use std::ops::{Add, Mul, Range, Sub};
+
+// Anything with a fixed number of coordinates that can go in a KdTree
+pub trait KdPoint: Copy {
+    type Coord: Copy
+        + PartialOrd
+        + Zero
+        + Add<Output = Self::Coord>
+        + Sub<Output = Self::Coord>
+        + Mul<Output = Self::Coord>;
+    const DIMS: usize;
+
+    fn coord(&self, axis: usize) -> Self::Coord;
+}
+
+impl<T> KdPoint for Point<T>
+where
+    T: Copy + PartialOrd + Zero + Sub<Output = T> + Mul<Output = T>,
+{
+    type Coord = T;
+    const DIMS: usize = 2;
+
+    fn coord(&self, axis: usize) -> T {
+        match axis {
+            0 => self.x,
+            _ => self.y,
+        }
+    }
+}
+
+impl<T> KdPoint for Point3<T>
+where
+    T: Copy + PartialOrd + Zero + Sub<Output = T> + Mul<Output = T>,
+{
+    type Coord = T;
+    const DIMS: usize = 3;
+
+    fn coord(&self, axis: usize) -> T {
+        match axis {
+            0 => self.x,
+            1 => self.y,
+            _ => self.z,
+        }
+    }
+}
+
+impl<T, const D: usize> KdPoint for PointN<T, D>
+where
+    T: Copy + PartialOrd + Zero + Sub<Output = T> + Mul<Output = T>,
+{
+    type Coord = T;
+    const DIMS: usize = D;
+
+    fn coord(&self, axis: usize) -> T {
+        self.coords[axis]
+    }
+}
+
+// Difference without going below zero, so unsigned coordinates work too
+fn abs_diff<T: Copy + PartialOrd + Sub<Output = T>>(a: T, b: T) -> T {
+    if a > b {
+        a - b
+    } else {
+        b - a
+    }
+}
+
+// Squared Euclidean distance, summed over every axis
+pub fn squared_distance<P: KdPoint>(a: &P, b: &P) -> P::Coord {
+    (0..P::DIMS).fold(P::Coord::zero(), |sum, axis| {
+        let d = abs_diff(a.coord(axis), b.coord(axis));
+        sum + d * d
+    })
+}
+
+// Order by distance then index, so ties always break the same way
+fn by_distance<T: PartialOrd>(a: &(T, usize), b: &(T, usize)) -> Ordering {
+    a.0.partial_cmp(&b.0)
+        .expect("distances must be comparable")
+        .then(a.1.cmp(&b.1))
+}
+
+// A static k-d tree, built once from a slice of points. Queries give back
+// indices into that slice along with squared Euclidean distances.
+//
+// The tree is implicit: each range of nodes has its median on the splitting
+// axis in the middle, smaller coordinates before it and larger after.
+#[derive(Debug, Clone)]
+pub struct KdTree<P> {
+    nodes: Vec<(P, usize)>,
+}
+
+impl<P: KdPoint> KdTree<P> {
+    pub fn new(points: &[P]) -> KdTree<P> {
+        let mut nodes: Vec<(P, usize)> = points.iter().copied().zip(0..).collect();
+        build(&mut nodes, 0);
+        KdTree { nodes }
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    // Index of the closest point and its squared distance
+    pub fn nearest(&self, target: &P) -> Option<(usize, P::Coord)> {
+        self.k_nearest(target, 1).first().map(|&(d, i)| (i, d))
+    }
+
+    // Up to k closest points as (squared distance, index), nearest first
+    pub fn k_nearest(&self, target: &P, k: usize) -> Vec<(P::Coord, usize)> {
+        let mut best = Vec::with_capacity(k + 1);
+        if k > 0 {
+            self.search_nearest(0..self.nodes.len(), 0, target, k, &mut best);
+        }
+        best
+    }
+
+    // Every point within squared distance r2 of target, as (squared distance,
+    // index), nearest first
+    pub fn within(&self, target: &P, r2: P::Coord) -> Vec<(P::Coord, usize)> {
+        let mut res = Vec::new();
+        self.search_within(0..self.nodes.len(), 0, target, r2, &mut res);
+        res.sort_by(by_distance);
+        res
+    }
+
+    // The k closest pairs of points, (squared distance, i, j) with i < j,
+    // closest first. Each pair in the answer is among the k nearest
+    // neighbours of both its points, so only those are looked at, not all
+    // n^2 pairs.
+    pub fn closest_pairs(&self, k: usize) -> impl Iterator<Item = (P::Coord, usize, usize)> {
+        let mut pairs = Vec::new();
+        for &(p, i) in &self.nodes {
+            for (d, j) in self.k_nearest(&p, k + 1) {
+                if j != i {
+                    pairs.push((d, i.min(j), i.max(j)));
+                }
+            }
+        }
+        pairs.sort_by(|a, b| by_distance(&(a.0, a.1), &(b.0, b.1)).then(a.2.cmp(&b.2)));
+        pairs.dedup_by(|a, b| a.1 == b.1 && a.2 == b.2);
+        pairs.truncate(k);
+        pairs.into_iter()
+    }
+
+    // The node in the middle of range, and the ranges either side of it with
+    // the one on the same side as target first
+    fn split(&self, range: Range<usize>, depth: usize, target: &P) -> (usize, [Range<usize>; 2]) {
+        let mid = (range.start + range.end) / 2;
+        let axis = depth % P::DIMS;
+        let (left, right) = (range.start..mid, mid + 1..range.end);
+        if target.coord(axis) < self.nodes[mid].0.coord(axis) {
+            (mid, [left, right])
+        } else {
+            (mid, [right, left])
+        }
+    }
+
+    // Squared distance from target to the splitting plane of a node
+    fn plane_distance(&self, mid: usize, depth: usize, target: &P) -> P::Coord {
+        let axis = depth % P::DIMS;
+        let d = abs_diff(target.coord(axis), self.nodes[mid].0.coord(axis));
+        d * d
+    }
+
+    fn search_nearest(
+        &self,
+        range: Range<usize>,
+        depth: usize,
+        target: &P,
+        k: usize,
+        best: &mut Vec<(P::Coord, usize)>,
+    ) {
+        if range.is_empty() {
+            return;
+        }
+        let (mid, [near, far]) = self.split(range, depth, target);
+        let (p, i) = self.nodes[mid];
+        let candidate = (squared_distance(&p, target), i);
+        let pos = best.partition_point(|b| by_distance(b, &candidate) == Ordering::Less);
+        if pos < k {
+            best.insert(pos, candidate);
+            best.truncate(k);
+        }
+        self.search_nearest(near, depth + 1, target, k, best);
+        // The far side can only help if it's no further than the worst so far
+        let plane = self.plane_distance(mid, depth, target);
+        if best.len() < k || plane <= best[k - 1].0 {
+            self.search_nearest(far, depth + 1, target, k, best);
+        }
+    }
+
+    fn search_within(
+        &self,
+        range: Range<usize>,
+        depth: usize,
+        target: &P,
+        r2: P::Coord,
+        res: &mut Vec<(P::Coord, usize)>,
+    ) {
+        if range.is_empty() {
+            return;
+        }
+        let (mid, [near, far]) = self.split(range, depth, target);
+        let (p, i) = self.nodes[mid];
+        let d = squared_distance(&p, target);
+        if d <= r2 {
+            res.push((d, i));
+        }
+        self.search_within(near, depth + 1, target, r2, res);
+        if self.plane_distance(mid, depth, target) <= r2 {
+            self.search_within(far, depth + 1, target, r2, res);
+        }
+    }
+}
+
+// Put the median on this depth's axis in the middle, then do each side
+fn build<P: KdPoint>(nodes: &mut [(P, usize)], depth: usize) {
+    if nodes.len() <= 1 {
+        return;
+    }
+    let axis = depth % P::DIMS;
+    let mid = nodes.len() / 2;
+    nodes.select_nth_unstable_by(mid, |a, b| {
+        a.0.coord(axis)
+            .partial_cmp(&b.0.coord(axis))
+            .expect("coordinates must be comparable")
+    });
+    let (left, right) = nodes.split_at_mut(mid);
+    build(left, depth + 1);
+    build(&mut right[1..], depth + 1);
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::kd_tree::{squared_distance, KdPoint, KdTree};
+    use crate::point::Point;
+    use crate::point3::Point3;
+    use crate::test_util::lcg;
+
+    fn brute_k_nearest<P: KdPoint>(points: &[P], target: &P, k: usize) -> Vec<(P::Coord, usize)>
+    where
+        P::Coord: Ord,
+    {
+        let mut all: Vec<(P::Coord, usize)> = points
+            .iter()
+            .enumerate()
+            .map(|(i, p)| (squared_distance(p, target), i))
+            .collect();
+        all.sort();
+        all.truncate(k);
+        all
+    }
+
+    #[test]
+    fn test_nearest_against_brute_force() {
+        let mut next = lcg(7);
+        let mut rand = || next(50);
+        let points: Vec<Point<i64>> = (0..300)
+            .map(|_| Point {
+                x: rand(),
+                y: rand(),
+            })
+            .collect();
+        let tree = KdTree::new(&points);
+        assert_eq!(tree.len(), 300);
+        for _ in 0..100 {
+            let target = Point {
+                x: rand() - 5,
+                y: rand() + 5,
+            };
+            let expected = brute_k_nearest(&points, &target, 10);
+            assert_eq!(tree.k_nearest(&target, 10), expected);
+            assert_eq!(tree.nearest(&target), Some((expected[0].1, expected[0].0)));
+            let r2 = 30;
+            let within: Vec<(i64, usize)> = brute_k_nearest(&points, &target, points.len())
+                .into_iter()
+                .filter(|&(d, _)| d <= r2)
+                .collect();
+            assert_eq!(tree.within(&target, r2), within);
+        }
+    }
+
+    #[test]
+    fn test_point3() {
+        let mut next = lcg(99);
+        let mut rand = || next(1000);
+        let points: Vec<Point3<i64>> = (0..200)
+            .map(|_| Point3 {
+                x: rand(),
+                y: rand(),
+                z: rand(),
+            })
+            .collect();
+        let tree = KdTree::new(&points);
+        for p in points.iter().step_by(7) {
+            assert_eq!(tree.k_nearest(p, 5), brute_k_nearest(&points, p, 5));
+        }
+    }
+
+    #[test]
+    fn test_closest_pairs() {
+        let mut next = lcg(3);
+        let mut rand = || next(1000);
+        // Plenty of duplicate coordinates, to exercise ties
+        let points: Vec<Point3<i64>> = (0..150)
+            .map(|_| Point3 {
+                x: rand() % 20,
+                y: rand(),
+                z: rand(),
+            })
+            .collect();
+        let mut all = Vec::new();
+        for i in 0..points.len() {
+            for j in i + 1..points.len() {
+                all.push((squared_distance(&points[i], &points[j]), i, j));
+            }
+        }
+        all.sort();
+        let tree = KdTree::new(&points);
+        for k in [1, 10, 100] {
+            let pairs: Vec<(i64, usize, usize)> = tree.closest_pairs(k).collect();
+            assert_eq!(pairs, all[..k]);
+        }
+    }
+
+    #[test]
+    fn test_other_coordinate_types() {
+        let points = [
+            Point { x: 0usize, y: 0 },
+            Point { x: 5, y: 5 },
+            Point { x: 9, y: 1 },
+        ];
+        let tree = KdTree::new(&points);
+        assert_eq!(tree.nearest(&Point { x: 8, y: 0 }), Some((2, 2)));
+
+        let points = [Point { x: 0.5, y: 0.5 }, Point { x: -1.0, y: 2.0 }];
+        let tree = KdTree::new(&points);
+        assert_eq!(tree.nearest(&Point { x: -0.5, y: 1.5 }), Some((1, 0.5)));
+
+        let empty: KdTree<Point<i32>> = KdTree::new(&[]);
+        assert!(empty.is_empty());
+        assert_eq!(empty.nearest(&Point { x: 0, y: 0 }), None);
+    }
+}
@@ -1,10 +1,34 @@
+pub mod affine_mod;
+pub mod continued_fraction;
+pub mod coordinate_compression;
+pub mod diamond;
+pub mod digits;
+pub mod direction;
 pub mod divisors;
 pub mod eratosthenes_sieve;
 pub mod fibonacci_gen;
+pub mod gf2;
+pub mod hex;
+pub mod integer_program;
+pub mod interpolation;
+pub mod intersection;
+pub mod interval_set;
+pub mod kd_tree;
+pub mod line;
+pub mod linear_system;
+pub mod modular;
 pub mod num_factors;
 pub mod point;
+pub mod point3;
+pub mod point_n;
+pub mod polygon;
 pub mod prime_factors;
+pub mod pythagorean;
+pub mod rect;
 pub mod sums;
+#[cfg(test)]
+mod test_util;
+pub mod torus;
 // pub mod graph;
 
 #[cfg(test)]
@@ -13,13 +37,21 @@ mod tests {
     use crate::eratosthenes_sieve::Sieve;
     use crate::fibonacci_gen::Fibseq;
     use crate::num_factors::{num_factors, num_factors_alt};
-    use crate::prime_factors::prime_factors;
+    use crate::prime_factors::{prime_factors, totient};
 
     #[test]
     fn test_prime_factors() {
         assert_eq!(prime_factors(84), vec![2, 2, 3, 7]);
     }
 
+    #[test]
+    fn test_totient() {
+        assert_eq!(totient(1), 1);
+        assert_eq!(totient(9), 6);
+        assert_eq!(totient(36), 12);
+        assert_eq!(totient(97), 96);
+    }
+
     #[test]
     fn test_num_factors() {
         // Some triangle numbers
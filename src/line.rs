@@ -0,0 +1,309 @@
+use crate::divisors::gcd;
+use crate::point::Point;
+use crate::rect::Rect;
+use num::{Integer, Signed};
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+
+// The smallest lattice step from a towards b, (b - a) over the gcd of its
+// components. Zero if a == b.
+pub fn lattice_step<T: Integer + Signed + Copy>(a: Point<T>, b: Point<T>) -> Point<T> {
+    let d = b - a;
+    let g = gcd(d.x.abs(), d.y.abs());
+    if g.is_zero() {
+        d
+    } else {
+        d / g
+    }
+}
+
+// Every lattice point exactly on the segment from a to b, both ends included
+pub fn lattice_points<T: Integer + Signed + Copy>(a: Point<T>, b: Point<T>) -> LatticePoints<T> {
+    LatticePoints {
+        next: Some(a),
+        end: b,
+        step: lattice_step(a, b),
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct LatticePoints<T> {
+    next: Option<Point<T>>,
+    end: Point<T>,
+    step: Point<T>,
+}
+
+impl<T: Integer + Signed + Copy> Iterator for LatticePoints<T> {
+    type Item = Point<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let current = self.next?;
+        self.next = (current != self.end).then(|| current + self.step);
+        Some(current)
+    }
+}
+
+// Range of t with min <= a + t * s < max, None if there isn't one
+fn steps_within<T: Integer + Signed + Copy>(a: T, s: T, min: T, max: T) -> Option<(T, T)> {
+    let last = max - T::one();
+    let (lo, hi) = if s.is_zero() {
+        if a < min || a > last {
+            return None;
+        }
+        return Some((T::zero(), T::zero()));
+    } else if s.is_positive() {
+        (
+            Integer::div_ceil(&(min - a), &s),
+            Integer::div_floor(&(last - a), &s),
+        )
+    } else {
+        (
+            Integer::div_ceil(&(last - a), &s),
+            Integer::div_floor(&(min - a), &s),
+        )
+    };
+    (lo <= hi).then_some((lo, hi))
+}
+
+// Every lattice point on the infinite line through a and b that lies inside
+// bounds, from one edge to the other in the direction a to b. Neither a nor b
+// has to be inside. Eg. the antinodes in line with a pair of antennas.
+pub fn line_within<T: Integer + Signed + Copy>(
+    a: Point<T>,
+    b: Point<T>,
+    bounds: &Rect<T>,
+) -> LatticePoints<T> {
+    let step = lattice_step(a, b);
+    let range = steps_within(a.x, step.x, bounds.min.x, bounds.max.x).and_then(|(x0, x1)| {
+        let (y0, y1) = steps_within(a.y, step.y, bounds.min.y, bounds.max.y)?;
+        // A zero step is free in that axis, so only the other one limits t
+        let (lo, hi) = match (step.x.is_zero(), step.y.is_zero()) {
+            (true, true) => (T::zero(), T::zero()),
+            (true, false) => (y0, y1),
+            (false, true) => (x0, x1),
+            (false, false) => (x0.max(y0), x1.min(y1)),
+        };
+        (lo <= hi).then_some((lo, hi))
+    });
+    match range {
+        Some((lo, hi)) => LatticePoints {
+            next: Some(a + step * lo),
+            end: a + step * hi,
+            step,
+        },
+        None => LatticePoints {
+            next: None,
+            end: a,
+            step,
+        },
+    }
+}
+
+// Bresenham's line from a to b, both ends included. Unlike lattice_points it
+// gives a connected run of cells for any slope, moving diagonally where it
+// has to.
+pub fn bresenham<T: Integer + Signed + Copy>(a: Point<T>, b: Point<T>) -> Bresenham<T> {
+    let d = b - a;
+    Bresenham {
+        next: Some(a),
+        end: b,
+        dx: d.x.abs(),
+        dy: -d.y.abs(),
+        step: d.signum(),
+        err: d.x.abs() - d.y.abs(),
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Bresenham<T> {
+    next: Option<Point<T>>,
+    end: Point<T>,
+    dx: T,
+    dy: T,
+    step: Point<T>,
+    err: T,
+}
+
+impl<T: Integer + Signed + Copy> Iterator for Bresenham<T> {
+    type Item = Point<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let current = self.next?;
+        if current == self.end {
+            self.next = None;
+            return Some(current);
+        }
+        let mut next = current;
+        let e2 = self.err + self.err;
+        if e2 >= self.dy {
+            self.err = self.err + self.dy;
+            next.x = next.x + self.step.x;
+        }
+        if e2 <= self.dx {
+            self.err = self.err + self.dx;
+            next.y = next.y + self.step.y;
+        }
+        self.next = Some(next);
+        Some(current)
+    }
+}
+
+// True if no blocker sits on a lattice point strictly between from and to
+pub fn line_of_sight<T>(from: Point<T>, to: Point<T>, blockers: &HashSet<Point<T>>) -> bool
+where
+    T: Integer + Signed + Copy + Hash,
+{
+    lattice_points(from, to)
+        .filter(|&p| p != from && p != to)
+        .all(|p| !blockers.contains(&p))
+}
+
+// The points that can be seen from `from`, the nearest one in each direction,
+// with every point blocking those behind it. `from` itself is ignored.
+pub fn visible_from<T>(from: Point<T>, points: &[Point<T>]) -> Vec<Point<T>>
+where
+    T: Integer + Signed + Copy + Hash,
+{
+    let mut nearest: HashMap<Point<T>, Point<T>> = HashMap::new();
+    for &p in points.iter().filter(|&&p| p != from) {
+        let dir = lattice_step(from, p);
+        nearest
+            .entry(dir)
+            .and_modify(|q| {
+                if from.taxicab_distance(p) < from.taxicab_distance(*q) {
+                    *q = p;
+                }
+            })
+            .or_insert(p);
+    }
+    let mut res: Vec<Point<T>> = nearest.into_values().collect();
+    res.sort();
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::line::{bresenham, lattice_points, line_of_sight, line_within, visible_from};
+    use crate::point::Point;
+    use crate::rect::Rect;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_lattice_points() {
+        let pts: Vec<Point<i32>> =
+            lattice_points(Point { x: 0, y: 0 }, Point { x: 6, y: -4 }).collect();
+        assert_eq!(
+            pts,
+            vec![
+                Point { x: 0, y: 0 },
+                Point { x: 3, y: -2 },
+                Point { x: 6, y: -4 }
+            ]
+        );
+        assert_eq!(
+            lattice_points(Point { x: 1, y: 1 }, Point { x: 1, y: 1 }).count(),
+            1
+        );
+        assert_eq!(
+            lattice_points(Point { x: 5, y: 2 }, Point { x: 1, y: 2 }).count(),
+            5
+        );
+    }
+
+    #[test]
+    fn test_line_within() {
+        let bounds = Rect::from_size(12, 12);
+        let a = Point { x: 4, y: 3 };
+        let b = Point { x: 5, y: 5 };
+        let line: Vec<Point<i64>> = line_within(a, b, &bounds).collect();
+        assert_eq!(line.first(), Some(&Point { x: 3, y: 1 }));
+        assert_eq!(line.last(), Some(&Point { x: 8, y: 11 }));
+        assert_eq!(line.len(), 6);
+        assert!(line.contains(&a) && line.contains(&b));
+        // Reversed pair runs the other way
+        let back: Vec<Point<i64>> = line_within(b, a, &bounds).collect();
+        assert_eq!(back, line.iter().rev().copied().collect::<Vec<_>>());
+        // Horizontal, and starting outside the bounds
+        let row: Vec<Point<i64>> =
+            line_within(Point { x: -5, y: 2 }, Point { x: -3, y: 2 }, &bounds).collect();
+        assert_eq!(row.len(), 12);
+        assert_eq!(
+            line_within(Point { x: 0, y: 20 }, Point { x: 1, y: 20 }, &bounds).count(),
+            0
+        );
+        assert_eq!(
+            line_within(Point { x: 3, y: 3 }, Point { x: 3, y: 3 }, &bounds).count(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_bresenham() {
+        let line: Vec<Point<i32>> = bresenham(Point { x: 0, y: 0 }, Point { x: 5, y: 2 }).collect();
+        assert_eq!(
+            line,
+            vec![
+                Point { x: 0, y: 0 },
+                Point { x: 1, y: 0 },
+                Point { x: 2, y: 1 },
+                Point { x: 3, y: 1 },
+                Point { x: 4, y: 2 },
+                Point { x: 5, y: 2 },
+            ]
+        );
+        // Every octant reaches the end in max(|dx|, |dy|) steps
+        for (x, y) in [(3, 7), (-7, 3), (-3, -7), (7, -3), (0, -4), (4, 4)] {
+            let end = Point { x, y };
+            let line: Vec<Point<i32>> = bresenham(Point { x: 0, y: 0 }, end).collect();
+            assert_eq!(line.len() as i32, x.abs().max(y.abs()) + 1);
+            assert_eq!(line.last(), Some(&end));
+            assert!(line.windows(2).all(|w| w[0].chebyshev_distance(w[1]) == 1));
+        }
+    }
+
+    #[test]
+    fn test_visibility() {
+        let map = ".#..#
+.....
+#####
+....#
+...##";
+        let asteroids: Vec<Point<i32>> = map
+            .lines()
+            .enumerate()
+            .flat_map(|(y, line)| {
+                line.chars()
+                    .enumerate()
+                    .filter(|&(_, c)| c == '#')
+                    .map(move |(x, _)| Point {
+                        x: x as i32,
+                        y: y as i32,
+                    })
+            })
+            .collect();
+        let best = asteroids
+            .iter()
+            .map(|&a| (visible_from(a, &asteroids).len(), a))
+            .max()
+            .unwrap();
+        assert_eq!(best, (8, Point { x: 3, y: 4 }));
+
+        // (2, 2) is in the way of (1, 0) but nothing is on the line to (4, 0)
+        let blockers: HashSet<Point<i32>> = asteroids.iter().copied().collect();
+        assert!(line_of_sight(
+            Point { x: 3, y: 4 },
+            Point { x: 4, y: 0 },
+            &blockers
+        ));
+        assert!(!line_of_sight(
+            Point { x: 3, y: 4 },
+            Point { x: 1, y: 0 },
+            &blockers
+        ));
+        assert!(!line_of_sight(
+            Point { x: 1, y: 0 },
+            Point { x: 1, y: 4 },
+            &blockers
+        ));
+    }
+}
@@ -0,0 +1,174 @@
+use crate::divisors::gcd;
+use num::rational::Ratio;
+use num::{abs, Zero};
+
+pub type Rational128 = Ratio<i128>;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Solution<T> {
+    Unique(Vec<T>),
+    NoSolution,
+    Infinite,
+}
+
+// Reduce an augmented matrix [A | b] in place to reduced row echelon form,
+// returning the pivot column of each leading row. The last column is treated as
+// the right hand side and is never chosen as a pivot.
+pub fn row_reduce(m: &mut [Vec<Rational128>]) -> Vec<usize> {
+    let cols = m.first().map_or(0, |row| row.len().saturating_sub(1));
+    let mut pivots = Vec::new();
+    for col in 0..cols {
+        let pivot_row = pivots.len();
+        let Some(pivot) = (pivot_row..m.len()).find(|&r| !m[r][col].is_zero()) else {
+            continue;
+        };
+        m.swap(pivot_row, pivot);
+        let p = m[pivot_row][col];
+        for v in m[pivot_row].iter_mut() {
+            *v /= p;
+        }
+        let pivot_values = m[pivot_row].clone();
+        for (r, row) in m.iter_mut().enumerate() {
+            if r != pivot_row && !row[col].is_zero() {
+                let factor = row[col];
+                for (v, p) in row.iter_mut().zip(&pivot_values).skip(col) {
+                    *v -= factor * p;
+                }
+            }
+        }
+        pivots.push(col);
+    }
+    pivots
+}
+
+// Solve Ax = b exactly by Gaussian elimination (reduced row echelon form).
+// A is m rows by n columns, it needn't be square.
+pub fn solve_rational(a: &[Vec<Rational128>], b: &[Rational128]) -> Solution<Rational128> {
+    assert_eq!(
+        a.len(),
+        b.len(),
+        "A and b must have the same number of rows"
+    );
+    let cols = a.first().map_or(0, |row| row.len());
+    // Augmented matrix [A | b]
+    let mut m: Vec<Vec<Rational128>> = a
+        .iter()
+        .zip(b)
+        .map(|(row, rhs)| {
+            assert_eq!(row.len(), cols, "A must be rectangular");
+            let mut row = row.clone();
+            row.push(*rhs);
+            row
+        })
+        .collect();
+
+    let pivot_row = row_reduce(&mut m).len();
+
+    // A zero row with a non-zero right hand side is a contradiction
+    if m[pivot_row..].iter().any(|row| !row[cols].is_zero()) {
+        return Solution::NoSolution;
+    }
+    if pivot_row < cols {
+        return Solution::Infinite;
+    }
+    Solution::Unique(m.iter().take(cols).map(|row| row[cols]).collect())
+}
+
+pub fn solve(a: &[Vec<i128>], b: &[i128]) -> Solution<Rational128> {
+    let a: Vec<Vec<Rational128>> = a
+        .iter()
+        .map(|row| row.iter().map(|&v| Ratio::from_integer(v)).collect())
+        .collect();
+    let b: Vec<Rational128> = b.iter().map(|&v| Ratio::from_integer(v)).collect();
+    solve_rational(&a, &b)
+}
+
+// As solve, but only accepts a unique solution that is entirely integral
+pub fn solve_integer(a: &[Vec<i128>], b: &[i128]) -> Option<Vec<i128>> {
+    match solve(a, b) {
+        Solution::Unique(x) if x.iter().all(|v| v.is_integer()) => {
+            Some(x.iter().map(|v| v.to_integer()).collect())
+        }
+        _ => None,
+    }
+}
+
+// Cramer's rule for
+//      a[0][0] * x + a[0][1] * y = b[0]
+//      a[1][0] * x + a[1][1] * y = b[1]
+// None if the determinant is zero or the solution isn't integral.
+pub fn solve_integer_2x2(a: [[i64; 2]; 2], b: [i64; 2]) -> Option<(i64, i64)> {
+    let [[a00, a01], [a10, a11]] = a.map(|row| row.map(i128::from));
+    let [b0, b1] = b.map(i128::from);
+    let det = a00 * a11 - a01 * a10;
+    if det == 0 {
+        return None;
+    }
+    let x = b0 * a11 - a01 * b1;
+    let y = a00 * b1 - b0 * a10;
+    // det divides both numerators exactly when it is their common divisor
+    if abs(gcd(x, det)) != abs(det) || abs(gcd(y, det)) != abs(det) {
+        return None;
+    }
+    Some(((x / det).try_into().ok()?, (y / det).try_into().ok()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn r(n: i128, d: i128) -> Rational128 {
+        Ratio::new(n, d)
+    }
+
+    #[test]
+    fn test_solve_unique() {
+        // x + y + z = 6, 2y + 5z = -4, 2x + 5y - z = 27
+        let a = vec![vec![1, 1, 1], vec![0, 2, 5], vec![2, 5, -1]];
+        assert_eq!(
+            solve(&a, &[6, -4, 27]),
+            Solution::Unique(vec![r(5, 1), r(3, 1), r(-2, 1)])
+        );
+        assert_eq!(solve_integer(&a, &[6, -4, 27]), Some(vec![5, 3, -2]));
+    }
+
+    #[test]
+    fn test_solve_fractional() {
+        let a = vec![vec![2, 0], vec![0, 3]];
+        assert_eq!(solve(&a, &[1, 1]), Solution::Unique(vec![r(1, 2), r(1, 3)]));
+        assert_eq!(solve_integer(&a, &[1, 1]), None);
+    }
+
+    #[test]
+    fn test_solve_degenerate() {
+        let a = vec![vec![1, 2], vec![2, 4]];
+        assert_eq!(solve(&a, &[3, 6]), Solution::Infinite);
+        assert_eq!(solve(&a, &[3, 7]), Solution::NoSolution);
+        // Overdetermined but consistent
+        let a = vec![vec![1, 0], vec![0, 1], vec![1, 1]];
+        assert_eq!(
+            solve(&a, &[1, 2, 3]),
+            Solution::Unique(vec![r(1, 1), r(2, 1)])
+        );
+        assert_eq!(solve(&a, &[1, 2, 4]), Solution::NoSolution);
+    }
+
+    #[test]
+    fn test_solve_integer_2x2() {
+        // Claw machine: A = (94, 34), B = (22, 67), prize at (8400, 5400)
+        assert_eq!(
+            solve_integer_2x2([[94, 22], [34, 67]], [8400, 5400]),
+            Some((80, 40))
+        );
+        // A = (26, 66), B = (67, 21), prize at (12748, 12176) has no integer answer
+        assert_eq!(
+            solve_integer_2x2([[26, 67], [66, 21]], [12748, 12176]),
+            None
+        );
+        assert_eq!(solve_integer_2x2([[1, 2], [2, 4]], [3, 6]), None);
+        assert_eq!(
+            solve_integer_2x2([[26, 67], [66, 21]], [10000000012748, 10000000012176]),
+            Some((118679050709, 103199174542))
+        );
+    }
+}
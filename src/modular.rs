@@ -0,0 +1,241 @@
+use crate::divisors::{gcd, gcd_extended};
+use crate::prime_factors::{prime_factors, totient};
+use itertools::Itertools;
+use std::collections::HashMap;
+
+// Products go via u128 so any u64 modulus is safe
+pub fn mod_mul(a: u64, b: u64, m: u64) -> u64 {
+    (a as u128 * b as u128 % m as u128) as u64
+}
+
+// Exponentiation by squaring
+pub fn mod_pow(base: u64, exp: u64, m: u64) -> u64 {
+    let mut result = 1 % m;
+    let mut base = base % m;
+    let mut exp = exp;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = mod_mul(result, base, m);
+        }
+        base = mod_mul(base, base, m);
+        exp >>= 1;
+    }
+    result
+}
+
+// None unless a and m are coprime
+pub fn mod_inverse(a: u64, m: u64) -> Option<u64> {
+    let (g, x, _) = gcd_extended(a as i128 % m as i128, m as i128);
+    if g != 1 {
+        return None;
+    }
+    Some(x.rem_euclid(m as i128) as u64)
+}
+
+// Chinese remainder theorem for coprime moduli, (residue, modulus) pairs.
+// Returns the smallest non-negative solution and the combined modulus, or
+// None if the moduli aren't coprime or their product doesn't fit in a u64.
+pub fn crt(congruences: &[(u64, u64)]) -> Option<(u64, u64)> {
+    congruences.iter().try_fold((0, 1), |(r1, m1), &(r2, m2)| {
+        let inv = mod_inverse(m1 % m2, m2)?;
+        // r = r1 + m1 * k where k = (r2 - r1) / m1 (mod m2)
+        let diff = (r2 as i128 - r1 as i128).rem_euclid(m2 as i128) as u64;
+        let k = mod_mul(diff, inv, m2);
+        let m = m1.checked_mul(m2)?;
+        Some(((r1 as u128 + m1 as u128 * k as u128) as u64 % m, m))
+    })
+}
+
+// Smallest k > 0 with a^k = 1 (mod m). None unless a and m are coprime.
+// The order divides phi(m), so strip prime factors from phi(m) while the
+// power still comes out as 1.
+pub fn multiplicative_order(a: u64, m: u64) -> Option<u64> {
+    if m == 1 {
+        return Some(1);
+    }
+    if gcd(a % m, m) != 1 {
+        return None;
+    }
+    let phi = totient(m as usize) as u64;
+    let order = prime_factors(phi as usize)
+        .into_iter()
+        .dedup()
+        .fold(phi, |mut order, p| {
+            let p = p as u64;
+            while order % p == 0 && mod_pow(a, order / p, m) == 1 {
+                order /= p;
+            }
+            order
+        });
+    Some(order)
+}
+
+// Smallest generator of the multiplicative group mod a prime p
+pub fn primitive_root(p: u64) -> Option<u64> {
+    if p == 2 {
+        return Some(1);
+    }
+    let phi = p - 1;
+    let factors: Vec<u64> = prime_factors(phi as usize)
+        .into_iter()
+        .dedup()
+        .map(|q| q as u64)
+        .collect();
+    (2..p).find(|&g| factors.iter().all(|&q| mod_pow(g, phi / q, p) != 1))
+}
+
+// Smallest x >= 0 with base^x = target (mod modulus), or None.
+// When base and modulus are coprime this is Pohlig-Hellman over the order of
+// base, with baby-step giant-step in each prime-order subgroup, so it is fast
+// when the order is smooth. Otherwise common factors are divided out first.
+pub fn dlog(base: u64, target: u64, modulus: u64) -> Option<u64> {
+    if modulus == 1 {
+        return Some(0);
+    }
+    let base = base % modulus;
+    let mut target = target % modulus;
+    let mut m = modulus;
+    // base^x = target reduces to coeff * base^(x - k) = target (mod m)
+    let mut coeff = 1;
+    let mut k = 0;
+    loop {
+        if coeff == target {
+            return Some(k);
+        }
+        let g = gcd(base, m);
+        if g == 1 {
+            break;
+        }
+        if !target.is_multiple_of(g) {
+            return None;
+        }
+        target /= g;
+        m /= g;
+        coeff = mod_mul(coeff, base / g, m);
+        k += 1;
+    }
+    let base = base % m;
+    let target = mod_mul(target, mod_inverse(coeff, m)?, m);
+    let order = multiplicative_order(base, m)?;
+    let congruences: Vec<(u64, u64)> = prime_factors(order as usize)
+        .into_iter()
+        .chunk_by(|&q| q)
+        .into_iter()
+        .map(|(q, group)| {
+            let (q, e) = (q as u64, group.count() as u32);
+            pohlig_hellman_prime_power(base, target, m, order, q, e).map(|x| (x, q.pow(e)))
+        })
+        .collect::<Option<_>>()?;
+    let (x, _) = crt(&congruences)?;
+    (mod_pow(base, x, m) == target).then_some(x + k)
+}
+
+// x mod q^e, where g has the given order and q^e divides it
+fn pohlig_hellman_prime_power(g: u64, h: u64, m: u64, order: u64, q: u64, e: u32) -> Option<u64> {
+    // gamma generates the subgroup of order q
+    let gamma = mod_pow(g, order / q, m);
+    let g_inv = mod_inverse(g, m)?;
+    let mut x = 0;
+    let mut q_k = 1;
+    for _ in 0..e {
+        // Strip the digits found so far and project into the order q subgroup
+        let h_k = mod_pow(mod_mul(mod_pow(g_inv, x, m), h, m), order / (q_k * q), m);
+        let d = baby_step_giant_step(gamma, h_k, m, q)?;
+        x += d * q_k;
+        q_k *= q;
+    }
+    Some(x)
+}
+
+// x in 0..n with g^x = h (mod m), where n bounds the order of g
+fn baby_step_giant_step(g: u64, h: u64, m: u64, n: u64) -> Option<u64> {
+    let steps = (n as f64).sqrt().ceil() as u64;
+    let mut baby = HashMap::new();
+    let mut value = 1 % m;
+    for j in 0..steps {
+        baby.entry(value).or_insert(j);
+        value = mod_mul(value, g, m);
+    }
+    // value is now g^steps
+    let giant = mod_inverse(value, m)?;
+    let mut gamma = h;
+    for i in 0..steps {
+        if let Some(j) = baby.get(&gamma) {
+            return Some(i * steps + j);
+        }
+        gamma = mod_mul(gamma, giant, m);
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mod_pow_and_inverse() {
+        assert_eq!(mod_pow(2, 10, 1000), 24);
+        assert_eq!(mod_pow(3, 0, 1), 0);
+        assert_eq!(mod_pow(u64::MAX - 1, 2, u64::MAX), 1);
+        assert_eq!(mod_inverse(3, 11), Some(4));
+        assert_eq!(mod_inverse(6, 9), None);
+    }
+
+    #[test]
+    fn test_crt() {
+        assert_eq!(crt(&[(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        assert_eq!(crt(&[]), Some((0, 1)));
+        assert_eq!(crt(&[(1, 4), (3, 6)]), None);
+        // Coprime, but the combined modulus is past u64::MAX
+        assert_eq!(crt(&[(1, 1 << 33), (2, (1 << 33) - 1)]), None);
+        let big = crt(&[(1, 1 << 32), (2, (1 << 32) - 1)]).unwrap();
+        assert_eq!((big.0 % (1 << 32), big.0 % ((1 << 32) - 1)), (1, 2));
+    }
+
+    #[test]
+    fn test_multiplicative_order() {
+        // Length of the repeating decimal of 1/d
+        assert_eq!(multiplicative_order(10, 7), Some(6));
+        assert_eq!(multiplicative_order(10, 3), Some(1));
+        assert_eq!(multiplicative_order(10, 983), Some(982));
+        assert_eq!(multiplicative_order(10, 12), None);
+        assert_eq!(multiplicative_order(2, 1), Some(1));
+    }
+
+    #[test]
+    fn test_primitive_root() {
+        assert_eq!(primitive_root(2), Some(1));
+        assert_eq!(primitive_root(7), Some(3));
+        // The handshake subject number generates the whole group
+        assert_eq!(primitive_root(20201227), Some(7));
+    }
+
+    #[test]
+    fn test_dlog() {
+        // Handshake: subject number 7, modulus 20201227
+        assert_eq!(dlog(7, 5764801, 20201227), Some(8));
+        assert_eq!(dlog(7, 17807724, 20201227), Some(11));
+        assert_eq!(dlog(2, 1, 11), Some(0));
+        assert_eq!(dlog(3, 13, 17), Some(4));
+        // 2 generates only the quadratic residues mod 7
+        assert_eq!(dlog(2, 3, 7), None);
+    }
+
+    #[test]
+    fn test_dlog_not_coprime() {
+        // 2^x mod 12: 1, 2, 4, 8, 4, 8, ...
+        assert_eq!(dlog(2, 8, 12), Some(3));
+        assert_eq!(dlog(2, 4, 12), Some(2));
+        assert_eq!(dlog(2, 1, 12), Some(0));
+        assert_eq!(dlog(2, 6, 12), None);
+        // Against brute force
+        for m in 2..40u64 {
+            for b in 0..m {
+                for t in 0..m {
+                    let brute = (0..2 * m).find(|&x| mod_pow(b, x, m) == t % m);
+                    assert_eq!(dlog(b, t, m), brute, "{}^x = {} mod {}", b, t, m);
+                }
+            }
+        }
+    }
+}
@@ -1,7 +1,9 @@
-use num::{abs, One, Signed, ToPrimitive, Zero};
+use crate::direction::{Direction, Direction8};
+use num::traits::Euclid;
+use num::{abs, One, Signed, Zero};
 use std::cmp::max;
-use std::iter::zip;
-use std::ops::{Add, Sub};
+use std::iter::{zip, Sum};
+use std::ops::{Add, AddAssign, Div, Mul, Neg, Rem, Sub, SubAssign};
 
 #[derive(Debug, Copy, Clone, Hash, Eq, PartialEq, Default, Ord, PartialOrd)]
 pub struct Point<T> {
@@ -31,6 +33,255 @@ impl<T: Sub<Output = T>> Sub for Point<T> {
     }
 }
 
+impl<T: Neg<Output = T>> Neg for Point<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self {
+            x: -self.x,
+            y: -self.y,
+        }
+    }
+}
+
+impl<T: AddAssign> AddAssign for Point<T> {
+    fn add_assign(&mut self, other: Self) {
+        self.x += other.x;
+        self.y += other.y;
+    }
+}
+
+impl<T: SubAssign> SubAssign for Point<T> {
+    fn sub_assign(&mut self, other: Self) {
+        self.x -= other.x;
+        self.y -= other.y;
+    }
+}
+
+// Scalar multiply, divide and remainder apply to both components
+
+impl<T: Mul<Output = T> + Clone> Mul<T> for Point<T> {
+    type Output = Self;
+
+    fn mul(self, scalar: T) -> Self::Output {
+        Self {
+            x: self.x * scalar.clone(),
+            y: self.y * scalar,
+        }
+    }
+}
+
+impl<T: Div<Output = T> + Clone> Div<T> for Point<T> {
+    type Output = Self;
+
+    fn div(self, scalar: T) -> Self::Output {
+        Self {
+            x: self.x / scalar.clone(),
+            y: self.y / scalar,
+        }
+    }
+}
+
+impl<T: Rem<Output = T> + Clone> Rem<T> for Point<T> {
+    type Output = Self;
+
+    fn rem(self, scalar: T) -> Self::Output {
+        Self {
+            x: self.x % scalar.clone(),
+            y: self.y % scalar,
+        }
+    }
+}
+
+impl<T: Add<Output = T> + Zero> Sum for Point<T> {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(
+            Point {
+                x: Zero::zero(),
+                y: Zero::zero(),
+            },
+            |acc, p| acc + p,
+        )
+    }
+}
+
+impl<T> From<(T, T)> for Point<T> {
+    fn from((x, y): (T, T)) -> Self {
+        Point { x, y }
+    }
+}
+
+impl<T> From<Point<T>> for (T, T) {
+    fn from(p: Point<T>) -> Self {
+        (p.x, p.y)
+    }
+}
+
+impl TryFrom<Point<i64>> for Point<usize> {
+    type Error = std::num::TryFromIntError;
+
+    fn try_from(p: Point<i64>) -> Result<Self, Self::Error> {
+        Ok(Point {
+            x: p.x.try_into()?,
+            y: p.y.try_into()?,
+        })
+    }
+}
+
+impl TryFrom<Point<usize>> for Point<i64> {
+    type Error = std::num::TryFromIntError;
+
+    fn try_from(p: Point<usize>) -> Result<Self, Self::Error> {
+        Ok(Point {
+            x: p.x.try_into()?,
+            y: p.y.try_into()?,
+        })
+    }
+}
+
+impl<T> Point<T> {
+    // Lossless conversion of both components, eg. Point<i32> to Point<i64>
+    pub fn cast<U: From<T>>(self) -> Point<U> {
+        Point {
+            x: U::from(self.x),
+            y: U::from(self.y),
+        }
+    }
+
+    // None if either component doesn't fit, eg. a negative neighbour of a
+    // Point<usize>
+    pub fn try_cast<U: TryFrom<T>>(self) -> Option<Point<U>> {
+        Some(Point {
+            x: U::try_from(self.x).ok()?,
+            y: U::try_from(self.y).ok()?,
+        })
+    }
+}
+
+impl<T> Point<T>
+where
+    T: Add<Output = T> + Sub<Output = T> + Mul<Output = T> + Clone,
+{
+    pub fn dot(&self, other: Self) -> T {
+        self.x.clone() * other.x + self.y.clone() * other.y
+    }
+
+    // 2D cross product, the determinant of [self other]. Positive when other
+    // is clockwise of self in screen coordinates (y down).
+    pub fn cross(&self, other: Self) -> T {
+        self.x.clone() * other.y - self.y.clone() * other.x
+    }
+}
+
+impl<T: Signed> Point<T> {
+    pub fn signum(&self) -> Self {
+        Point {
+            x: self.x.signum(),
+            y: self.y.signum(),
+        }
+    }
+
+    pub fn abs(&self) -> Self {
+        Point {
+            x: self.x.abs(),
+            y: self.y.abs(),
+        }
+    }
+}
+
+impl<T: Euclid> Point<T> {
+    // Component-wise, eg. wrapping onto a width x height grid
+    pub fn rem_euclid(&self, modulus: &Self) -> Self {
+        Point {
+            x: self.x.rem_euclid(&modulus.x),
+            y: self.y.rem_euclid(&modulus.y),
+        }
+    }
+}
+
+// Rotations are in screen coordinates (y down), so rotate_right is clockwise
+// as drawn: North (0, -1) turns to East (1, 0).
+impl<T> Point<T>
+where
+    T: Neg<Output = T> + Add<Output = T> + Sub<Output = T> + Clone,
+{
+    pub fn rotate_right(&self) -> Self {
+        Point {
+            x: -self.y.clone(),
+            y: self.x.clone(),
+        }
+    }
+
+    pub fn rotate_left(&self) -> Self {
+        Point {
+            x: self.y.clone(),
+            y: -self.x.clone(),
+        }
+    }
+
+    pub fn rotate_180(&self) -> Self {
+        -self.clone()
+    }
+
+    pub fn rotate_right_about(&self, pivot: &Self) -> Self {
+        (self.clone() - pivot.clone()).rotate_right() + pivot.clone()
+    }
+
+    pub fn rotate_left_about(&self, pivot: &Self) -> Self {
+        (self.clone() - pivot.clone()).rotate_left() + pivot.clone()
+    }
+
+    pub fn rotate_180_about(&self, pivot: &Self) -> Self {
+        (self.clone() - pivot.clone()).rotate_180() + pivot.clone()
+    }
+
+    // Mirror in the x axis, y -> -y
+    pub fn reflect_x(&self) -> Self {
+        Point {
+            x: self.x.clone(),
+            y: -self.y.clone(),
+        }
+    }
+
+    // Mirror in the y axis, x -> -x
+    pub fn reflect_y(&self) -> Self {
+        Point {
+            x: -self.x.clone(),
+            y: self.y.clone(),
+        }
+    }
+
+    // Mirror in the line y = x
+    pub fn reflect_diagonal(&self) -> Self {
+        Point {
+            x: self.y.clone(),
+            y: self.x.clone(),
+        }
+    }
+
+    // Mirror in the line y = -x
+    pub fn reflect_antidiagonal(&self) -> Self {
+        Point {
+            x: -self.y.clone(),
+            y: -self.x.clone(),
+        }
+    }
+
+    // All 8 images under rotation and reflection about the origin: the 4
+    // rotations (0, 90, 180, 270 clockwise), then each of those reflected in
+    // the y axis
+    pub fn orientations(&self) -> Vec<Self> {
+        let mut res = vec![self.clone()];
+        for i in 0..3 {
+            res.push(res[i].rotate_right());
+        }
+        for i in 0..4 {
+            res.push(res[i].reflect_y());
+        }
+        res
+    }
+}
+
 impl Point<f32> {
     #[allow(dead_code)]
     fn distance_from_origin(&self) -> f32 {
@@ -38,30 +289,76 @@ impl Point<f32> {
     }
 }
 
+// Neighbours on a width x height grid of Point<usize>, without any signed
+// conversions by the caller. Same orders as cardinal_points and compass_points.
 impl Point<usize> {
+    fn offset_within(&self, offset: Point<isize>, width: usize, height: usize) -> Option<Self> {
+        let x = self.x.checked_add_signed(offset.x)?;
+        let y = self.y.checked_add_signed(offset.y)?;
+        (x < width && y < height).then_some(Point { x, y })
+    }
+
+    // None on a grid with no cells, where there's nowhere to wrap to
+    fn offset_wrapping(&self, offset: Point<isize>, width: usize, height: usize) -> Option<Self> {
+        // Widening to i128 is lossless, and the result is below the size
+        let wrap = |v: usize, d: isize, size: usize| {
+            (size > 0).then(|| (v as i128 + d as i128).rem_euclid(size as i128) as usize)
+        };
+        Some(Point {
+            x: wrap(self.x, offset.x, width)?,
+            y: wrap(self.y, offset.y, height)?,
+        })
+    }
+
+    // N, E, S, W neighbours, None where a coordinate would go below zero
     pub fn cardinal_usize(&self) -> Vec<Option<Point<usize>>> {
-        let mut res: Vec<Option<Point<usize>>> = Vec::new();
-        let offsets = vec![
-            Point { x: 0, y: -1 }, // N
-            Point { x: 1, y: 0 },  // E
-            Point { x: 0, y: 1 },  // S
-            Point { x: -1, y: 0 }, // W
-        ];
-        for offset in offsets {
-            let next_pos = Point {
-                x: self.x as i32,
-                y: self.y as i32,
-            } + offset;
-            if !next_pos.x.is_negative() || next_pos.y.is_negative() {
-                res.push(Some(Point {
-                    x: next_pos.x.to_usize().unwrap(),
-                    y: next_pos.y.to_usize().unwrap(),
-                }));
-            } else {
-                res.push(None);
-            }
-        }
-        res
+        Direction::ALL
+            .into_iter()
+            .map(|d| self.checked_step(d, 1))
+            .collect()
+    }
+
+    // N, E, S, W neighbours that are inside the grid
+    pub fn cardinal_within(&self, width: usize, height: usize) -> impl Iterator<Item = Self> {
+        let pt = *self;
+        Direction::ALL
+            .into_iter()
+            .filter_map(move |d| pt.offset_within(d.offset(), width, height))
+    }
+
+    // Clockwise from N, the neighbours that are inside the grid
+    pub fn compass_within(&self, width: usize, height: usize) -> impl Iterator<Item = Self> {
+        let pt = *self;
+        Direction8::ALL
+            .into_iter()
+            .filter_map(move |d| pt.offset_within(d.offset(), width, height))
+    }
+
+    // n steps in a Direction or Direction8, None if that leaves usize
+    pub fn checked_step(&self, dir: impl Into<Direction8>, n: usize) -> Option<Self> {
+        let offset: Point<isize> = dir.into().offset();
+        let n = isize::try_from(n).ok()?;
+        Some(Point {
+            x: self.x.checked_add_signed(offset.x.checked_mul(n)?)?,
+            y: self.y.checked_add_signed(offset.y.checked_mul(n)?)?,
+        })
+    }
+
+    // N, E, S, W neighbours on a torus, wrapping off one edge onto the other.
+    // Nothing when width or height is zero.
+    pub fn cardinal_wrapping(&self, width: usize, height: usize) -> impl Iterator<Item = Self> {
+        let pt = *self;
+        Direction::ALL
+            .into_iter()
+            .filter_map(move |d| pt.offset_wrapping(d.offset(), width, height))
+    }
+
+    // Clockwise from N, the neighbours on a torus
+    pub fn compass_wrapping(&self, width: usize, height: usize) -> impl Iterator<Item = Self> {
+        let pt = *self;
+        Direction8::ALL
+            .into_iter()
+            .filter_map(move |d| pt.offset_wrapping(d.offset(), width, height))
     }
 }
 
@@ -156,6 +453,17 @@ where
     }
 }
 
+impl<T: Signed + Copy> Point<T> {
+    // n steps in a Direction or Direction8
+    pub fn step(&self, dir: impl Into<Direction8>, n: T) -> Self {
+        let offset: Point<T> = dir.into().offset();
+        Point {
+            x: self.x + offset.x * n,
+            y: self.y + offset.y * n,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::point::Point;
@@ -175,6 +483,162 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_operators() {
+        let mut pt = Point { x: 3, y: -4 };
+        assert_eq!(-pt, Point { x: -3, y: 4 });
+        assert_eq!(pt * 2, Point { x: 6, y: -8 });
+        assert_eq!(pt / 2, Point { x: 1, y: -2 });
+        assert_eq!(pt % 2, Point { x: 1, y: 0 });
+        pt += Point { x: 1, y: 1 };
+        assert_eq!(pt, Point { x: 4, y: -3 });
+        pt -= Point { x: 4, y: 4 };
+        assert_eq!(pt, Point { x: 0, y: -7 });
+        assert_eq!(
+            pt.rem_euclid(&Point { x: 11, y: 5 }),
+            Point { x: 0, y: 3 }
+        );
+        let total: Point<i32> = (1..=3).map(|i| Point { x: i, y: 1 }).sum();
+        assert_eq!(total, Point { x: 6, y: 3 });
+    }
+
+    #[test]
+    fn test_products() {
+        let a = Point { x: 2, y: 3 };
+        let b = Point { x: -1, y: 4 };
+        assert_eq!(a.dot(b), 10);
+        assert_eq!(a.cross(b), 11);
+        assert_eq!(b.signum(), Point { x: -1, y: 1 });
+        assert_eq!(b.abs(), Point { x: 1, y: 4 });
+    }
+
+    #[test]
+    fn test_conversions() {
+        let pt: Point<i64> = (3, -4).into();
+        assert_eq!(pt, Point { x: 3, y: -4 });
+        assert_eq!(<(i64, i64)>::from(pt), (3, -4));
+        assert!(Point::<usize>::try_from(pt).is_err());
+        assert_eq!(
+            Point::<usize>::try_from(Point { x: 3i64, y: 4 }),
+            Ok(Point { x: 3, y: 4 })
+        );
+        assert_eq!(
+            Point::<i64>::try_from(Point { x: 3usize, y: 4 }),
+            Ok(Point { x: 3, y: 4 })
+        );
+        assert_eq!(pt.try_cast::<usize>(), None);
+        assert_eq!(Point { x: 1u8, y: 2 }.cast::<i64>(), Point { x: 1, y: 2 });
+    }
+
+    #[test]
+    fn test_rotations() {
+        let pt = Point { x: 2, y: -1 };
+        assert_eq!(pt.rotate_right(), Point { x: 1, y: 2 });
+        assert_eq!(pt.rotate_left(), Point { x: -1, y: -2 });
+        assert_eq!(pt.rotate_180(), Point { x: -2, y: 1 });
+        assert_eq!(pt.rotate_right().rotate_left(), pt);
+        let pivot = Point { x: 1, y: 1 };
+        assert_eq!(pt.rotate_right_about(&pivot), Point { x: 3, y: 2 });
+        assert_eq!(pt.rotate_left_about(&pivot), Point { x: -1, y: 0 });
+        assert_eq!(pt.rotate_180_about(&pivot), Point { x: 0, y: 3 });
+    }
+
+    #[test]
+    fn test_reflections() {
+        let pt = Point { x: 2, y: -1 };
+        assert_eq!(pt.reflect_x(), Point { x: 2, y: 1 });
+        assert_eq!(pt.reflect_y(), Point { x: -2, y: -1 });
+        assert_eq!(pt.reflect_diagonal(), Point { x: -1, y: 2 });
+        assert_eq!(pt.reflect_antidiagonal(), Point { x: 1, y: -2 });
+        let mut all = pt.orientations();
+        assert_eq!(all.len(), 8);
+        all.sort();
+        all.dedup();
+        assert_eq!(all.len(), 8);
+    }
+
+    #[test]
+    fn test_cardinal_usize() {
+        let pt: Point<usize> = Point { x: 0, y: 1 };
+        assert_eq!(
+            pt.cardinal_usize(),
+            vec![
+                Some(Point { x: 0, y: 0 }),
+                Some(Point { x: 1, y: 1 }),
+                Some(Point { x: 0, y: 2 }),
+                None,
+            ]
+        );
+        let pt: Point<usize> = Point { x: 1, y: 0 };
+        assert_eq!(pt.cardinal_usize()[0], None);
+        // Past i32::MAX, where going via i32 used to wrap
+        let x = 1 << 32;
+        let pt: Point<usize> = Point { x, y: 0 };
+        assert_eq!(
+            pt.cardinal_usize(),
+            vec![
+                None,
+                Some(Point { x: x + 1, y: 0 }),
+                Some(Point { x, y: 1 }),
+                Some(Point { x: x - 1, y: 0 }),
+            ]
+        );
+        let y = usize::MAX;
+        let pt: Point<usize> = Point { x: 0, y };
+        assert_eq!(pt.cardinal_usize()[2], None);
+    }
+
+    #[test]
+    fn test_neighbours_within() {
+        let corner: Point<usize> = Point { x: 0, y: 0 };
+        assert_eq!(
+            corner.cardinal_within(3, 2).collect::<Vec<_>>(),
+            vec![Point { x: 1, y: 0 }, Point { x: 0, y: 1 }]
+        );
+        assert_eq!(corner.compass_within(3, 2).count(), 3);
+        let far: Point<usize> = Point { x: 2, y: 1 };
+        assert_eq!(
+            far.cardinal_within(3, 2).collect::<Vec<_>>(),
+            vec![Point { x: 2, y: 0 }, Point { x: 1, y: 1 }]
+        );
+        let middle: Point<usize> = Point { x: 1, y: 1 };
+        assert_eq!(middle.compass_within(3, 3).count(), 8);
+        assert_eq!(Point { x: 0, y: 0 }.cardinal_within(1, 1).count(), 0);
+    }
+
+    #[test]
+    fn test_neighbours_wrapping() {
+        let corner: Point<usize> = Point { x: 0, y: 0 };
+        assert_eq!(
+            corner.cardinal_wrapping(3, 2).collect::<Vec<_>>(),
+            vec![
+                Point { x: 0, y: 1 },
+                Point { x: 1, y: 0 },
+                Point { x: 0, y: 1 },
+                Point { x: 2, y: 0 },
+            ]
+        );
+        assert!(corner.compass_wrapping(4, 4).all(|p| p.x < 4 && p.y < 4));
+        assert!(corner
+            .compass_wrapping(4, 4)
+            .any(|p| p == Point { x: 3, y: 3 }));
+        // No cells, so no neighbours rather than a divide by zero
+        assert_eq!(corner.cardinal_wrapping(0, 3).count(), 0);
+        assert_eq!(corner.compass_wrapping(3, 0).count(), 0);
+        // Past isize::MAX, where casting to isize would go negative
+        let x = usize::MAX - 1;
+        let far: Point<usize> = Point { x, y: 0 };
+        assert_eq!(
+            far.cardinal_wrapping(usize::MAX, 1).collect::<Vec<_>>(),
+            vec![
+                Point { x, y: 0 },
+                Point { x: 0, y: 0 },
+                Point { x, y: 0 },
+                Point { x: x - 1, y: 0 },
+            ]
+        );
+    }
+
     #[test]
     fn test_cardinal_points() {
         let pt: Point<isize> = Point { x: 3, y: 4 };
@@ -0,0 +1,267 @@
+use num::{abs, One, Signed, ToPrimitive, Zero};
+use std::array;
+use std::ops::{Add, Sub};
+
+#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq, Default, Ord, PartialOrd)]
+pub struct Point3<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+impl<T: Add<Output = T>> Add for Point3<T> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self::Output {
+        Self {
+            x: self.x + other.x,
+            y: self.y + other.y,
+            z: self.z + other.z,
+        }
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Point3<T> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self::Output {
+        Self {
+            x: self.x - other.x,
+            y: self.y - other.y,
+            z: self.z - other.z,
+        }
+    }
+}
+
+impl<T> Point3<T>
+where
+    T: Signed + Clone + Ord,
+{
+    pub fn taxicab_distance(&self, other: Self) -> T {
+        abs(self.x.clone() - other.x)
+            + abs(self.y.clone() - other.y)
+            + abs(self.z.clone() - other.z)
+    }
+
+    pub fn chebyshev_distance(&self, other: Self) -> T {
+        abs(self.x.clone() - other.x)
+            .max(abs(self.y.clone() - other.y))
+            .max(abs(self.z.clone() - other.z))
+    }
+}
+
+impl<T> Point3<T>
+where
+    T: Copy + Add<Output = T> + Sub<Output = T> + std::ops::Mul<Output = T>,
+{
+    // Exact, so safe for comparing distances between integer points
+    pub fn squared_distance(&self, other: Self) -> T {
+        let d = *self - other;
+        d.x * d.x + d.y * d.y + d.z * d.z
+    }
+}
+
+impl<T> Point3<T>
+where
+    T: Copy + Sub<Output = T> + ToPrimitive,
+{
+    pub fn euclidean_distance(&self, other: Self) -> f64 {
+        let d = |a: T, b: T| (a - b).to_f64().unwrap();
+        let (dx, dy, dz) = (d(self.x, other.x), d(self.y, other.y), d(self.z, other.z));
+        (dx * dx + dy * dy + dz * dz).sqrt()
+    }
+}
+
+impl<T> Point3<T>
+where
+    T: Copy + PartialEq + Add<Output = T> + Sub<Output = T> + Zero + One,
+{
+    // The 6 face neighbours: -x, +x, -y, +y, -z, +z
+    pub fn axis_neighbours(&self) -> Vec<Self> {
+        let (zero, one) = (T::zero(), T::one());
+        let minus_one = zero - one;
+        let offsets = [
+            Point3 {
+                x: minus_one,
+                y: zero,
+                z: zero,
+            },
+            Point3 {
+                x: one,
+                y: zero,
+                z: zero,
+            },
+            Point3 {
+                x: zero,
+                y: minus_one,
+                z: zero,
+            },
+            Point3 {
+                x: zero,
+                y: one,
+                z: zero,
+            },
+            Point3 {
+                x: zero,
+                y: zero,
+                z: minus_one,
+            },
+            Point3 {
+                x: zero,
+                y: zero,
+                z: one,
+            },
+        ];
+        offsets.iter().map(|&offset| *self + offset).collect()
+    }
+
+    // All 26 neighbours sharing a face, edge or corner
+    pub fn moore_neighbours(&self) -> Vec<Self> {
+        let (zero, one) = (T::zero(), T::one());
+        let steps = [zero - one, zero, one];
+        let mut res = Vec::new();
+        for &x in &steps {
+            for &y in &steps {
+                for &z in &steps {
+                    if (x, y, z) != (zero, zero, zero) {
+                        res.push(*self + Point3 { x, y, z });
+                    }
+                }
+            }
+        }
+        res
+    }
+}
+
+// The 24 proper rotations that keep the lattice on itself: signed permutation
+// matrices with determinant +1. The identity comes first.
+pub fn rotation_matrices() -> Vec<[[i8; 3]; 3]> {
+    // Each permutation of the axes with its parity
+    let perms = [
+        ([0, 1, 2], 1),
+        ([1, 2, 0], 1),
+        ([2, 0, 1], 1),
+        ([0, 2, 1], -1),
+        ([2, 1, 0], -1),
+        ([1, 0, 2], -1),
+    ];
+    let mut res = Vec::new();
+    for (perm, parity) in perms {
+        for signs in 0..8 {
+            let s: [i8; 3] = array::from_fn(|i| if signs >> i & 1 == 1 { -1 } else { 1 });
+            // A reflection for every odd sign flip or odd permutation
+            if parity * s[0] * s[1] * s[2] == 1 {
+                let mut m = [[0; 3]; 3];
+                for row in 0..3 {
+                    m[row][perm[row]] = s[row];
+                }
+                res.push(m);
+            }
+        }
+    }
+    res
+}
+
+impl<T> Point3<T>
+where
+    T: Copy + Add<Output = T> + Sub<Output = T> + Zero,
+{
+    // Multiply by one of rotation_matrices()
+    pub fn rotate(&self, m: &[[i8; 3]; 3]) -> Self {
+        let coords = [self.x, self.y, self.z];
+        let row = |r: &[i8; 3]| {
+            r.iter()
+                .zip(coords)
+                .fold(T::zero(), |acc, (&k, v)| match k {
+                    1 => acc + v,
+                    -1 => acc - v,
+                    _ => acc,
+                })
+        };
+        Point3 {
+            x: row(&m[0]),
+            y: row(&m[1]),
+            z: row(&m[2]),
+        }
+    }
+
+    pub fn rotate_about(&self, pivot: &Self, m: &[[i8; 3]; 3]) -> Self {
+        (*self - *pivot).rotate(m) + *pivot
+    }
+
+    // The point under each of the 24 rotations, in rotation_matrices() order
+    pub fn orientations(&self) -> Vec<Self> {
+        rotation_matrices().iter().map(|m| self.rotate(m)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::point3::{rotation_matrices, Point3};
+
+    #[test]
+    fn test_add_sub_point3() {
+        let a = Point3 { x: 1, y: 0, z: -2 };
+        let b = Point3 { x: 2, y: 3, z: 4 };
+        assert_eq!(a + b, Point3 { x: 3, y: 3, z: 2 });
+        assert_eq!(
+            a - b,
+            Point3 {
+                x: -1,
+                y: -3,
+                z: -6
+            }
+        );
+    }
+
+    #[test]
+    fn test_distances() {
+        let a = Point3 { x: 1, y: 2, z: 3 };
+        let b = Point3 { x: 4, y: -2, z: 3 };
+        assert_eq!(a.taxicab_distance(b), 7);
+        assert_eq!(a.chebyshev_distance(b), 4);
+        assert_eq!(a.squared_distance(b), 25);
+        assert_eq!(a.euclidean_distance(b), 5.0);
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let p: Point3<i32> = Point3 { x: 1, y: 1, z: 1 };
+        let axis = p.axis_neighbours();
+        assert_eq!(axis.len(), 6);
+        assert!(axis.iter().all(|&n| p.taxicab_distance(n) == 1));
+        let moore = p.moore_neighbours();
+        assert_eq!(moore.len(), 26);
+        assert!(moore.iter().all(|&n| p.chebyshev_distance(n) == 1));
+    }
+
+    #[test]
+    fn test_rotation_matrices() {
+        let matrices = rotation_matrices();
+        assert_eq!(matrices.len(), 24);
+        assert_eq!(matrices[0], [[1, 0, 0], [0, 1, 0], [0, 0, 1]]);
+        for m in &matrices {
+            let det = m[0][0] * (m[1][1] * m[2][2] - m[1][2] * m[2][1])
+                - m[0][1] * (m[1][0] * m[2][2] - m[1][2] * m[2][0])
+                + m[0][2] * (m[1][0] * m[2][1] - m[1][1] * m[2][0]);
+            assert_eq!(det, 1);
+        }
+    }
+
+    #[test]
+    fn test_orientations() {
+        let p = Point3 { x: 1, y: 2, z: 3 };
+        let mut all = p.orientations();
+        all.sort();
+        all.dedup();
+        assert_eq!(all.len(), 24);
+        // 90 degrees about z: x -> y, y -> -x
+        let rot_z = [[0, -1, 0], [1, 0, 0], [0, 0, 1]];
+        assert!(rotation_matrices().contains(&rot_z));
+        assert_eq!(p.rotate(&rot_z), Point3 { x: -2, y: 1, z: 3 });
+        let pivot = Point3 { x: 1, y: 1, z: 0 };
+        assert_eq!(p.rotate_about(&pivot, &rot_z), Point3 { x: 0, y: 1, z: 3 });
+        // A mirror image is not among the rotations
+        assert!(!all.contains(&Point3 { x: -1, y: 2, z: 3 }));
+    }
+}
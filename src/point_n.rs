@@ -0,0 +1,186 @@
+use crate::point::Point;
+use crate::point3::Point3;
+use num::{abs, One, Signed, ToPrimitive, Zero};
+use std::array;
+use std::ops::{Add, Mul, Sub};
+
+// A point in D dimensions, eg. PointN<i64, 4> for spacetime constellations
+#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq, Ord, PartialOrd)]
+pub struct PointN<T, const D: usize> {
+    pub coords: [T; D],
+}
+
+impl<T, const D: usize> PointN<T, D> {
+    pub fn new(coords: [T; D]) -> Self {
+        PointN { coords }
+    }
+}
+
+impl<T: Default, const D: usize> Default for PointN<T, D> {
+    fn default() -> Self {
+        PointN {
+            coords: array::from_fn(|_| T::default()),
+        }
+    }
+}
+
+impl<T: Copy + Add<Output = T>, const D: usize> Add for PointN<T, D> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self::Output {
+        PointN {
+            coords: array::from_fn(|i| self.coords[i] + other.coords[i]),
+        }
+    }
+}
+
+impl<T: Copy + Sub<Output = T>, const D: usize> Sub for PointN<T, D> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self::Output {
+        PointN {
+            coords: array::from_fn(|i| self.coords[i] - other.coords[i]),
+        }
+    }
+}
+
+impl<T> From<Point<T>> for PointN<T, 2> {
+    fn from(p: Point<T>) -> Self {
+        PointN { coords: [p.x, p.y] }
+    }
+}
+
+impl<T> From<Point3<T>> for PointN<T, 3> {
+    fn from(p: Point3<T>) -> Self {
+        PointN {
+            coords: [p.x, p.y, p.z],
+        }
+    }
+}
+
+impl<T, const D: usize> PointN<T, D>
+where
+    T: Signed + Copy + Ord,
+{
+    pub fn taxicab_distance(&self, other: Self) -> T {
+        (0..D).fold(T::zero(), |sum, i| {
+            sum + abs(self.coords[i] - other.coords[i])
+        })
+    }
+
+    pub fn chebyshev_distance(&self, other: Self) -> T {
+        (0..D).fold(T::zero(), |m, i| {
+            m.max(abs(self.coords[i] - other.coords[i]))
+        })
+    }
+}
+
+impl<T, const D: usize> PointN<T, D>
+where
+    T: Copy + Zero + Sub<Output = T> + Mul<Output = T>,
+{
+    pub fn squared_distance(&self, other: Self) -> T {
+        (0..D).fold(T::zero(), |sum, i| {
+            let d = self.coords[i] - other.coords[i];
+            sum + d * d
+        })
+    }
+}
+
+impl<T, const D: usize> PointN<T, D>
+where
+    T: Copy + Sub<Output = T> + ToPrimitive,
+{
+    pub fn euclidean_distance(&self, other: Self) -> f64 {
+        (0..D)
+            .map(|i| (self.coords[i] - other.coords[i]).to_f64().unwrap().powi(2))
+            .sum::<f64>()
+            .sqrt()
+    }
+}
+
+impl<T, const D: usize> PointN<T, D>
+where
+    T: Copy + PartialEq + Add<Output = T> + Sub<Output = T> + Zero + One,
+{
+    // The 2 * D neighbours one step along a single axis, in axis order, minus
+    // then plus
+    pub fn axis_neighbours(&self) -> Vec<Self> {
+        let minus_one = T::zero() - T::one();
+        let mut res = Vec::with_capacity(2 * D);
+        for axis in 0..D {
+            for step in [minus_one, T::one()] {
+                let mut next = *self;
+                next.coords[axis] = next.coords[axis] + step;
+                res.push(next);
+            }
+        }
+        res
+    }
+
+    // All 3^D - 1 neighbours within one step on every axis
+    pub fn moore_neighbours(&self) -> Vec<Self> {
+        let steps = [T::zero() - T::one(), T::zero(), T::one()];
+        let total = 3usize.pow(D as u32);
+        let mut res = Vec::with_capacity(total - 1);
+        // Count in base 3, one digit per axis
+        for n in 0..total {
+            let mut rest = n;
+            let offset: [T; D] = array::from_fn(|_| {
+                let step = steps[rest % 3];
+                rest /= 3;
+                step
+            });
+            if offset.iter().any(|&v| v != T::zero()) {
+                res.push(*self + PointN::new(offset));
+            }
+        }
+        res
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::point::Point;
+    use crate::point3::Point3;
+    use crate::point_n::PointN;
+
+    #[test]
+    fn test_add_sub_point_n() {
+        let a = PointN::new([1, 2, 3, 4]);
+        let b = PointN::new([0, -1, 5, 2]);
+        assert_eq!(a + b, PointN::new([1, 1, 8, 6]));
+        assert_eq!(a - b, PointN::new([1, 3, -2, 2]));
+    }
+
+    #[test]
+    fn test_distances() {
+        let a = PointN::new([0, 0, 0, 0]);
+        let b = PointN::new([3, 0, -3, 1]);
+        assert_eq!(a.taxicab_distance(b), 7);
+        assert_eq!(a.chebyshev_distance(b), 3);
+        assert_eq!(a.squared_distance(b), 19);
+        assert_eq!(
+            PointN::new([3, 4]).euclidean_distance(PointN::new([0, 0])),
+            5.0
+        );
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let p: PointN<i64, 4> = PointN::default();
+        assert_eq!(p.axis_neighbours().len(), 8);
+        let moore = p.moore_neighbours();
+        assert_eq!(moore.len(), 80);
+        assert!(moore.iter().all(|&n| p.chebyshev_distance(n) == 1));
+    }
+
+    #[test]
+    fn test_from() {
+        assert_eq!(PointN::from(Point { x: 1, y: 2 }), PointN::new([1, 2]));
+        assert_eq!(
+            PointN::from(Point3 { x: 1, y: 2, z: 3 }),
+            PointN::new([1, 2, 3])
+        );
+    }
+}
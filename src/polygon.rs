@@ -0,0 +1,410 @@
+use crate::coordinate_compression::CoordinateCompressor;
+use crate::direction::Direction;
+use crate::divisors::gcd;
+use crate::intersection::{orientation, wide, within};
+use crate::point::Point;
+use num::rational::Ratio;
+
+// Simple polygon on the integer lattice. The vertices go round the loop in
+// order (either way) and the last joins back to the first.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Polygon {
+    pub vertices: Vec<Point<i64>>,
+}
+
+impl Polygon {
+    pub fn new(vertices: Vec<Point<i64>>) -> Polygon {
+        Polygon { vertices }
+    }
+
+    // Follow (direction, length) moves from start, eg. a dig plan. The closing
+    // move back to start is optional.
+    pub fn from_moves<I>(start: Point<i64>, moves: I) -> Polygon
+    where
+        I: IntoIterator<Item = (Direction, i64)>,
+    {
+        let mut vertices = vec![start];
+        let mut pos = start;
+        for (dir, n) in moves {
+            pos = pos.step(dir, n);
+            vertices.push(pos);
+        }
+        if vertices.len() > 1 && vertices.last() == Some(&start) {
+            vertices.pop();
+        }
+        Polygon { vertices }
+    }
+
+    fn edges(&self) -> impl Iterator<Item = (Point<i64>, Point<i64>)> + '_ {
+        self.vertices
+            .iter()
+            .zip(self.vertices.iter().cycle().skip(1))
+            .map(|(&a, &b)| (a, b))
+    }
+
+    // Shoelace formula, 2A = |sum of x_i * y_(i+1) - x_(i+1) * y_i|. Always an
+    // integer for lattice vertices, where the area itself may be a half.
+    pub fn twice_area(&self) -> i64 {
+        let twice_area = self
+            .edges()
+            .map(|(a, b)| wide(a).cross(wide(b)))
+            .sum::<i128>()
+            .abs();
+        i64::try_from(twice_area).expect("polygon area too large for i64")
+    }
+
+    pub fn area(&self) -> Ratio<i64> {
+        Ratio::new(self.twice_area(), 2)
+    }
+
+    // Lattice points on the edges. An edge crosses gcd(|dx|, |dy|) of them,
+    // counting one end but not the other.
+    pub fn boundary_points(&self) -> i64 {
+        self.edges()
+            .map(|(a, b)| {
+                let d = (b - a).abs();
+                gcd(d.x, d.y)
+            })
+            .sum()
+    }
+
+    // Pick's theorem, A = i + b/2 - 1
+    pub fn interior_points(&self) -> i64 {
+        (self.twice_area() - self.boundary_points() + 2) / 2
+    }
+
+    // Interior and boundary together, eg. every cube dug out of a lagoon
+    pub fn lattice_points(&self) -> i64 {
+        self.interior_points() + self.boundary_points()
+    }
+
+    // On an edge, including the vertices
+    pub fn on_boundary(&self, p: &Point<i64>) -> bool {
+        self.edges()
+            .any(|(a, b)| orientation(a, b, *p) == 0 && within(a, b, *p))
+    }
+
+    // Number of times the polygon winds round p, zero when p is outside. Only
+    // meaningful for points that aren't on the boundary.
+    pub fn winding_number(&self, p: &Point<i64>) -> i64 {
+        self.edges().fold(0, |wn, (a, b)| {
+            let side = orientation(a, b, *p);
+            if a.y <= p.y && b.y > p.y && side > 0 {
+                wn + 1
+            } else if a.y > p.y && b.y <= p.y && side < 0 {
+                wn - 1
+            } else {
+                wn
+            }
+        })
+    }
+
+    // Strictly inside, points on an edge are not included
+    pub fn contains_strict(&self, p: &Point<i64>) -> bool {
+        !self.on_boundary(p) && self.winding_number(p) != 0
+    }
+
+    // Inside or on an edge
+    pub fn contains(&self, p: &Point<i64>) -> bool {
+        self.on_boundary(p) || self.winding_number(p) != 0
+    }
+
+    // Largest axis-aligned rectangle with opposite corners on two of the given
+    // tiles that lies entirely inside this rectilinear polygon, edges included.
+    // Area is in tiles, so counts both corner rows and columns. Returns the area
+    // and the two corners.
+    //
+    // The plane is compressed to just the x and y values that appear, so this
+    // works for coordinates far too large to draw out.
+    pub fn largest_rectangle_inside(
+        &self,
+        tiles: &[Point<i64>],
+    ) -> Option<(i64, Point<i64>, Point<i64>)> {
+        let compressor = CoordinateCompressor::new(self.vertices.iter().chain(tiles).copied());
+        let grid = compressor.grid();
+        let cell_of = |p: Point<i64>| grid.cell(&p).unwrap();
+        let (width, height) = (grid.width(), grid.height());
+
+        let mut boundary = vec![vec![false; width]; height];
+        for (a, b) in self.edges() {
+            assert!(a.x == b.x || a.y == b.y, "polygon must be rectilinear");
+            let (ca, cb) = (cell_of(a), cell_of(b));
+            let (c0, c1) = (ca.x.min(cb.x), ca.x.max(cb.x));
+            let (r0, r1) = (ca.y.min(cb.y), ca.y.max(cb.y));
+            for cells in &mut boundary[r0..=r1] {
+                for cell in &mut cells[c0..=c1] {
+                    *cell = true;
+                }
+            }
+        }
+
+        // Flood the outside in from the border
+        let mut outside = vec![vec![false; width]; height];
+        let mut stack = vec![Point { x: 0, y: 0 }];
+        outside[0][0] = true;
+        while let Some(pos) = stack.pop() {
+            for next in pos.cardinal_within(width, height) {
+                if !boundary[next.y][next.x] && !outside[next.y][next.x] {
+                    outside[next.y][next.x] = true;
+                    stack.push(next);
+                }
+            }
+        }
+
+        // prefix[r][c] is the outside area of cells above and left of (r, c)
+        let mut prefix = vec![vec![0i64; width + 1]; height + 1];
+        for r in 0..height {
+            for c in 0..width {
+                let area = if outside[r][c] {
+                    grid.cell_area(&Point { x: c, y: r })
+                } else {
+                    0
+                };
+                prefix[r + 1][c + 1] = area + prefix[r][c + 1] + prefix[r + 1][c] - prefix[r][c];
+            }
+        }
+
+        let mut best: Option<(i64, Point<i64>, Point<i64>)> = None;
+        for (i, &a) in tiles.iter().enumerate() {
+            for &b in &tiles[i + 1..] {
+                let d = (b - a).abs();
+                let area = (d.x + 1) * (d.y + 1);
+                if best.is_some_and(|(best_area, _, _)| area <= best_area) {
+                    continue;
+                }
+                let (ca, cb) = (cell_of(a), cell_of(b));
+                let (c0, c1) = (ca.x.min(cb.x), ca.x.max(cb.x) + 1);
+                let (r0, r1) = (ca.y.min(cb.y), ca.y.max(cb.y) + 1);
+                let outside_area =
+                    prefix[r1][c1] - prefix[r0][c1] - prefix[r1][c0] + prefix[r0][c0];
+                if outside_area == 0 {
+                    best = Some((area, a, b));
+                }
+            }
+        }
+        best
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::direction::Direction;
+    use crate::point::Point;
+    use crate::polygon::Polygon;
+    use num::rational::Ratio;
+
+    const DIG_PLAN: &str = "R 6 (#70c710)
+D 5 (#0dc571)
+L 2 (#5713f0)
+D 2 (#d2c081)
+R 2 (#59c680)
+D 2 (#411b91)
+L 5 (#8ceee2)
+U 2 (#caa173)
+L 1 (#1b58a2)
+U 2 (#caa171)
+R 2 (#7807d2)
+U 3 (#a77fa3)
+L 2 (#015232)
+U 2 (#7a21e3)";
+
+    #[test]
+    fn test_area() {
+        let square = Polygon::new(vec![
+            Point { x: 0, y: 0 },
+            Point { x: 4, y: 0 },
+            Point { x: 4, y: 4 },
+            Point { x: 0, y: 4 },
+        ]);
+        assert_eq!(square.twice_area(), 32);
+        assert_eq!(square.boundary_points(), 16);
+        assert_eq!(square.interior_points(), 9);
+        assert_eq!(square.lattice_points(), 25);
+
+        let triangle = Polygon::new(vec![
+            Point { x: 0, y: 0 },
+            Point { x: 3, y: 0 },
+            Point { x: 0, y: 1 },
+        ]);
+        assert_eq!(triangle.area(), Ratio::new(3, 2));
+        assert_eq!(triangle.boundary_points(), 5);
+        assert_eq!(triangle.interior_points(), 0);
+    }
+
+    #[test]
+    fn test_dig_plan() {
+        let moves = DIG_PLAN.lines().map(|line| {
+            let mut parts = line.split_whitespace();
+            let dir: Direction = parts.next().unwrap().parse().unwrap();
+            (dir, parts.next().unwrap().parse().unwrap())
+        });
+        let lagoon = Polygon::from_moves(Point { x: 0, y: 0 }, moves);
+        assert_eq!(lagoon.vertices.len(), 14);
+        assert_eq!(lagoon.lattice_points(), 62);
+    }
+
+    #[test]
+    fn test_dig_plan_hex() {
+        let moves = DIG_PLAN.lines().map(|line| {
+            let hex = &line[line.find('#').unwrap() + 1..line.len() - 1];
+            let n = i64::from_str_radix(&hex[..5], 16).unwrap();
+            let dir = Direction::ALL[(hex.as_bytes()[5] - b'0' + 1) as usize % 4];
+            (dir, n)
+        });
+        let lagoon = Polygon::from_moves(Point { x: 0, y: 0 }, moves);
+        assert_eq!(lagoon.lattice_points(), 952408144115);
+    }
+
+    #[test]
+    fn test_point_in_polygon() {
+        // L shape
+        let l = Polygon::new(vec![
+            Point { x: 0, y: 0 },
+            Point { x: 2, y: 0 },
+            Point { x: 2, y: 2 },
+            Point { x: 4, y: 2 },
+            Point { x: 4, y: 4 },
+            Point { x: 0, y: 4 },
+        ]);
+        assert!(l.contains_strict(&Point { x: 1, y: 1 }));
+        assert!(l.contains_strict(&Point { x: 3, y: 3 }));
+        assert!(!l.contains(&Point { x: 3, y: 1 }));
+        assert!(!l.contains(&Point { x: 5, y: 3 }));
+        assert!(l.on_boundary(&Point { x: 3, y: 2 }));
+        assert!(l.contains(&Point { x: 3, y: 2 }));
+        assert!(!l.contains_strict(&Point { x: 3, y: 2 }));
+        assert!(l.contains(&Point { x: 0, y: 4 }));
+        // Either orientation
+        let mut reversed = l.clone();
+        reversed.vertices.reverse();
+        assert_eq!(
+            reversed.winding_number(&Point { x: 1, y: 1 }),
+            -l.winding_number(&Point { x: 1, y: 1 })
+        );
+        assert!(reversed.contains_strict(&Point { x: 1, y: 1 }));
+    }
+
+    #[test]
+    fn test_large_coordinates() {
+        // Each cross product is past i64::MAX though the area is tiny
+        let far = 4_000_000_000;
+        let triangle = Polygon::new(vec![
+            Point { x: far, y: far },
+            Point { x: far + 3, y: far },
+            Point { x: far, y: far + 1 },
+        ]);
+        assert_eq!(triangle.twice_area(), 3);
+        assert!(triangle.on_boundary(&Point { x: far + 1, y: far }));
+
+        let square = Polygon::new(vec![
+            Point { x: -far, y: -far },
+            Point { x: far, y: -far },
+            Point { x: far, y: far },
+            Point { x: -far, y: far },
+        ]);
+        assert!(square.contains_strict(&Point { x: 0, y: 0 }));
+        assert!(square.on_boundary(&Point { x: far, y: 12345 }));
+        assert!(!square.contains(&Point { x: far + 1, y: 0 }));
+    }
+
+    #[test]
+    fn test_largest_rectangle_inside() {
+        let tiles = vec![
+            Point { x: 7, y: 1 },
+            Point { x: 11, y: 1 },
+            Point { x: 11, y: 7 },
+            Point { x: 9, y: 7 },
+            Point { x: 9, y: 5 },
+            Point { x: 2, y: 5 },
+            Point { x: 2, y: 3 },
+            Point { x: 7, y: 3 },
+        ];
+        let floor = Polygon::new(tiles.clone());
+        let (area, a, b) = floor.largest_rectangle_inside(&tiles).unwrap();
+        assert_eq!(area, 24);
+        assert_eq!((a, b), (Point { x: 9, y: 5 }, Point { x: 2, y: 3 }));
+    }
+
+    // Every pair of vertices, every tile in between checked with contains
+    fn brute_force_largest(polygon: &Polygon) -> i64 {
+        let v = &polygon.vertices;
+        let mut best = 0;
+        for (i, a) in v.iter().enumerate() {
+            for b in &v[i + 1..] {
+                let inside = (a.x.min(b.x)..=a.x.max(b.x)).all(|x| {
+                    (a.y.min(b.y)..=a.y.max(b.y)).all(|y| polygon.contains(&Point { x, y }))
+                });
+                if inside {
+                    let d = (*b - *a).abs();
+                    best = best.max((d.x + 1) * (d.y + 1));
+                }
+            }
+        }
+        best
+    }
+
+    #[test]
+    fn test_largest_rectangle_against_brute_force() {
+        // A notch with no tiles between its walls doesn't split the rectangle
+        let notch = Polygon::new(vec![
+            Point { x: 0, y: 0 },
+            Point { x: 5, y: 0 },
+            Point { x: 5, y: 3 },
+            Point { x: 6, y: 3 },
+            Point { x: 6, y: 0 },
+            Point { x: 10, y: 0 },
+            Point { x: 10, y: 5 },
+            Point { x: 0, y: 5 },
+        ]);
+        let (area, _, _) = notch.largest_rectangle_inside(&notch.vertices).unwrap();
+        assert_eq!(area, 11 * 6);
+        assert_eq!(area, brute_force_largest(&notch));
+
+        // A wider notch does
+        let notch = Polygon::new(vec![
+            Point { x: 0, y: 0 },
+            Point { x: 4, y: 0 },
+            Point { x: 4, y: 3 },
+            Point { x: 6, y: 3 },
+            Point { x: 6, y: 0 },
+            Point { x: 10, y: 0 },
+            Point { x: 10, y: 5 },
+            Point { x: 0, y: 5 },
+        ]);
+        let (area, _, _) = notch.largest_rectangle_inside(&notch.vertices).unwrap();
+        assert_eq!(area, brute_force_largest(&notch));
+
+        let stairs = Polygon::new(vec![
+            Point { x: 0, y: 0 },
+            Point { x: 3, y: 0 },
+            Point { x: 3, y: 2 },
+            Point { x: 6, y: 2 },
+            Point { x: 6, y: 4 },
+            Point { x: 9, y: 4 },
+            Point { x: 9, y: 9 },
+            Point { x: 0, y: 9 },
+        ]);
+        let (area, _, _) = stairs.largest_rectangle_inside(&stairs.vertices).unwrap();
+        assert_eq!(area, brute_force_largest(&stairs));
+    }
+
+    #[test]
+    fn test_largest_rectangle_large_coordinates() {
+        let l = Polygon::new(vec![
+            Point { x: 0, y: 0 },
+            Point { x: 20000, y: 0 },
+            Point { x: 20000, y: 90000 },
+            Point {
+                x: 100000,
+                y: 90000,
+            },
+            Point {
+                x: 100000,
+                y: 100000,
+            },
+            Point { x: 0, y: 100000 },
+        ]);
+        let (area, _, _) = l.largest_rectangle_inside(&l.vertices).unwrap();
+        assert_eq!(area, 20001 * 100001);
+    }
+}
@@ -1,3 +1,5 @@
+use itertools::Itertools;
+
 pub fn prime_factors(of: usize) -> Vec<usize> {
     /*
     Trial Division
@@ -9,7 +11,7 @@ pub fn prime_factors(of: usize) -> Vec<usize> {
     let mut n = of;
     // x only goes as far as sqrt(n)
     while x * x <= n {
-        while n % x == 0 {
+        while n.is_multiple_of(x) {
             prime_factors.push(x);
             n /= x;
         }
@@ -24,3 +26,12 @@ pub fn prime_factors(of: usize) -> Vec<usize> {
 pub fn is_prime(n: usize) -> bool {
     prime_factors(n).len() == 1
 }
+
+// Euler's totient, the count of 1..=n coprime to n
+//      phi(n) = n * (1 - 1/p1) * (1 - 1/p2) ... for each distinct prime p
+pub fn totient(n: usize) -> usize {
+    prime_factors(n)
+        .into_iter()
+        .dedup()
+        .fold(n, |phi, p| phi / p * (p - 1))
+}
@@ -0,0 +1,150 @@
+use crate::divisors::gcd;
+use std::collections::BTreeMap;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Limit {
+    // a + b + c <= limit
+    Perimeter(usize),
+    // c <= limit
+    Hypotenuse(usize),
+}
+
+impl Limit {
+    fn allows(&self, (a, b, c): (usize, usize, usize)) -> bool {
+        match *self {
+            Limit::Perimeter(p) => a + b + c <= p,
+            Limit::Hypotenuse(h) => c <= h,
+        }
+    }
+}
+
+// Pythagorean triples (a, b, c) with a < b < c, by Euclid's formula
+//      a = m^2 - n^2, b = 2mn, c = m^2 + n^2
+// which gives every primitive triple exactly once when m > n > 0, m and n are
+// coprime and not both odd. Multiples k * (a, b, c) follow each primitive.
+#[derive(Debug)]
+pub struct Triples {
+    limit: Limit,
+    include_multiples: bool,
+    m: usize,
+    n: usize,
+    k: usize,
+    primitive: Option<(usize, usize, usize)>,
+}
+
+impl Triples {
+    pub fn primitive(limit: Limit) -> Triples {
+        Triples {
+            limit,
+            include_multiples: false,
+            m: 2,
+            n: 0,
+            k: 1,
+            primitive: None,
+        }
+    }
+
+    pub fn all(limit: Limit) -> Triples {
+        Triples {
+            include_multiples: true,
+            ..Triples::primitive(limit)
+        }
+    }
+}
+
+fn euclid(m: usize, n: usize) -> (usize, usize, usize) {
+    let (a, b) = (m * m - n * n, 2 * m * n);
+    (a.min(b), a.max(b), m * m + n * n)
+}
+
+impl Iterator for Triples {
+    type Item = (usize, usize, usize);
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some((a, b, c)) = self.primitive {
+                let k = self.k;
+                let triple = (k * a, k * b, k * c);
+                if k == 1 || (self.include_multiples && self.limit.allows(triple)) {
+                    self.k += 1;
+                    return Some(triple);
+                }
+                self.primitive = None;
+            }
+            self.n += 1;
+            if self.n >= self.m {
+                self.m += 1;
+                self.n = 0;
+                // n = 1 gives the smallest triple for this m, and it only grows
+                if !self.limit.allows(euclid(self.m, 1)) {
+                    return None;
+                }
+                continue;
+            }
+            if (self.m - self.n) % 2 == 1 && gcd(self.m, self.n) == 1 {
+                let triple = euclid(self.m, self.n);
+                if self.limit.allows(triple) {
+                    self.primitive = Some(triple);
+                    self.k = 1;
+                } else {
+                    // Larger n only makes it bigger, move on to the next m
+                    self.n = self.m;
+                }
+            }
+        }
+    }
+}
+
+// All triples, including multiples, grouped by perimeter
+pub fn triples_by_perimeter(max_perimeter: usize) -> BTreeMap<usize, Vec<(usize, usize, usize)>> {
+    Triples::all(Limit::Perimeter(max_perimeter)).fold(BTreeMap::new(), |mut m, t| {
+        m.entry(t.0 + t.1 + t.2).or_insert_with(Vec::new).push(t);
+        m
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_primitive() {
+        let mut triples: Vec<_> = Triples::primitive(Limit::Hypotenuse(30)).collect();
+        triples.sort();
+        assert_eq!(
+            triples,
+            vec![
+                (3, 4, 5),
+                (5, 12, 13),
+                (7, 24, 25),
+                (8, 15, 17),
+                (20, 21, 29)
+            ]
+        );
+        assert_eq!(Triples::primitive(Limit::Hypotenuse(100)).count(), 16);
+        assert_eq!(Triples::primitive(Limit::Perimeter(11)).count(), 0);
+    }
+
+    #[test]
+    fn test_all() {
+        let mut triples: Vec<_> = Triples::all(Limit::Hypotenuse(15)).collect();
+        triples.sort();
+        assert_eq!(
+            triples,
+            vec![(3, 4, 5), (5, 12, 13), (6, 8, 10), (9, 12, 15)]
+        );
+        for (a, b, c) in Triples::all(Limit::Perimeter(1000)) {
+            assert_eq!(a * a + b * b, c * c);
+            assert!(a < b && a + b + c <= 1000);
+        }
+    }
+
+    #[test]
+    fn test_by_perimeter() {
+        let groups = triples_by_perimeter(1000);
+        assert_eq!(groups[&12], vec![(3, 4, 5)]);
+        assert_eq!(groups[&120].len(), 3);
+        // Perimeter with the most solutions
+        let (p, _) = groups.iter().max_by_key(|(_, v)| v.len()).unwrap();
+        assert_eq!(*p, 840);
+    }
+}
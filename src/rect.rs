@@ -0,0 +1,331 @@
+use crate::point::Point;
+use num::{One, Zero};
+use std::ops::{Add, Mul, Sub};
+
+// Axis-aligned rectangle, half-open: min is inside, max is just outside, like
+// a Range in each axis. Empty when either side has no width.
+//
+// It's always stored half-open rather than carrying a flag, so two rects
+// compare equal whichever way they were made. Which convention a max corner
+// follows is chosen where it goes in or comes out instead, with Bounds.
+#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq)]
+pub struct Rect<T> {
+    pub min: Point<T>,
+    pub max: Point<T>,
+}
+
+// Whether a max corner is inside the rectangle or just outside it
+#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq)]
+pub enum Bounds {
+    HalfOpen,
+    Inclusive,
+}
+
+impl<T> Rect<T>
+where
+    T: Copy + Ord + Zero + One + Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
+{
+    // From min (inside) to max (outside)
+    pub fn new(min: Point<T>, max: Point<T>) -> Rect<T> {
+        Rect { min, max }
+    }
+
+    // Both corners inside, in either order
+    pub fn inclusive(a: Point<T>, b: Point<T>) -> Rect<T> {
+        Rect {
+            min: Point {
+                x: a.x.min(b.x),
+                y: a.y.min(b.y),
+            },
+            max: Point {
+                x: a.x.max(b.x) + T::one(),
+                y: a.y.max(b.y) + T::one(),
+            },
+        }
+    }
+
+    // From min to max, with max inside or not depending on bounds
+    pub fn with_bounds(min: Point<T>, max: Point<T>, bounds: Bounds) -> Rect<T> {
+        match bounds {
+            Bounds::HalfOpen => Rect::new(min, max),
+            Bounds::Inclusive => Rect::inclusive(min, max),
+        }
+    }
+
+    // 0..width, 0..height
+    pub fn from_size(width: T, height: T) -> Rect<T> {
+        Rect {
+            min: Point {
+                x: T::zero(),
+                y: T::zero(),
+            },
+            max: Point {
+                x: width,
+                y: height,
+            },
+        }
+    }
+
+    // Smallest rectangle containing every point, None if there are none
+    pub fn bounding<I: IntoIterator<Item = Point<T>>>(points: I) -> Option<Rect<T>> {
+        points
+            .into_iter()
+            .map(|p| Rect::inclusive(p, p))
+            .reduce(|a, b| a.union(&b))
+    }
+
+    pub fn width(&self) -> T {
+        if self.max.x > self.min.x {
+            self.max.x - self.min.x
+        } else {
+            T::zero()
+        }
+    }
+
+    pub fn height(&self) -> T {
+        if self.max.y > self.min.y {
+            self.max.y - self.min.y
+        } else {
+            T::zero()
+        }
+    }
+
+    pub fn area(&self) -> T {
+        self.width() * self.height()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.width().is_zero() || self.height().is_zero()
+    }
+
+    pub fn contains(&self, p: &Point<T>) -> bool {
+        self.min.x <= p.x && p.x < self.max.x && self.min.y <= p.y && p.y < self.max.y
+    }
+
+    // Corners inside the rectangle, y down so top is min.y. An empty rectangle
+    // has none, so these panic on one rather than underflow.
+    pub fn top_left(&self) -> Point<T> {
+        self.assert_not_empty();
+        self.min
+    }
+
+    pub fn top_right(&self) -> Point<T> {
+        self.assert_not_empty();
+        Point {
+            x: self.max.x - T::one(),
+            y: self.min.y,
+        }
+    }
+
+    pub fn bottom_left(&self) -> Point<T> {
+        self.assert_not_empty();
+        Point {
+            x: self.min.x,
+            y: self.max.y - T::one(),
+        }
+    }
+
+    pub fn bottom_right(&self) -> Point<T> {
+        self.assert_not_empty();
+        Point {
+            x: self.max.x - T::one(),
+            y: self.max.y - T::one(),
+        }
+    }
+
+    // The max corner under either convention, the inverse of with_bounds
+    pub fn max_corner(&self, bounds: Bounds) -> Point<T> {
+        match bounds {
+            Bounds::HalfOpen => self.max,
+            Bounds::Inclusive => self.bottom_right(),
+        }
+    }
+
+    fn assert_not_empty(&self) {
+        assert!(!self.is_empty(), "empty rectangle has no corners");
+    }
+
+    // None if they don't overlap
+    pub fn intersection(&self, other: &Rect<T>) -> Option<Rect<T>> {
+        let rect = Rect {
+            min: Point {
+                x: self.min.x.max(other.min.x),
+                y: self.min.y.max(other.min.y),
+            },
+            max: Point {
+                x: self.max.x.min(other.max.x),
+                y: self.max.y.min(other.max.y),
+            },
+        };
+        (!rect.is_empty()).then_some(rect)
+    }
+
+    // The smallest rectangle containing both. Empty ones contain nothing, so
+    // where they happen to be doesn't stretch the result.
+    pub fn union(&self, other: &Rect<T>) -> Rect<T> {
+        if self.is_empty() {
+            return *other;
+        }
+        if other.is_empty() {
+            return *self;
+        }
+        Rect {
+            min: Point {
+                x: self.min.x.min(other.min.x),
+                y: self.min.y.min(other.min.y),
+            },
+            max: Point {
+                x: self.max.x.max(other.max.x),
+                y: self.max.y.max(other.max.y),
+            },
+        }
+    }
+
+    // Grow by n on every side
+    pub fn expand(&self, n: T) -> Rect<T> {
+        Rect {
+            min: Point {
+                x: self.min.x - n,
+                y: self.min.y - n,
+            },
+            max: Point {
+                x: self.max.x + n,
+                y: self.max.y + n,
+            },
+        }
+    }
+
+    // Every point inside, row by row
+    pub fn points(&self) -> RectPoints<T> {
+        RectPoints {
+            rect: *self,
+            next: (!self.is_empty()).then_some(self.min),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct RectPoints<T> {
+    rect: Rect<T>,
+    next: Option<Point<T>>,
+}
+
+impl<T> Iterator for RectPoints<T>
+where
+    T: Copy + Ord + One + Add<Output = T>,
+{
+    type Item = Point<T>;
+    fn next(&mut self) -> Option<Self::Item> {
+        let current = self.next?;
+        let mut next = Point {
+            x: current.x + T::one(),
+            y: current.y,
+        };
+        if next.x >= self.rect.max.x {
+            next = Point {
+                x: self.rect.min.x,
+                y: current.y + T::one(),
+            };
+        }
+        self.next = (next.y < self.rect.max.y).then_some(next);
+        Some(current)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::point::Point;
+    use crate::rect::{Bounds, Rect};
+
+    #[test]
+    fn test_size() {
+        let r = Rect::new(Point { x: 1, y: 2 }, Point { x: 4, y: 4 });
+        assert_eq!(r.width(), 3);
+        assert_eq!(r.height(), 2);
+        assert_eq!(r.area(), 6);
+        assert_eq!(
+            Rect::inclusive(Point { x: 3, y: 3 }, Point { x: 1, y: 2 }),
+            r
+        );
+        assert!(Rect::new(Point { x: 1, y: 1 }, Point { x: 1, y: 5 }).is_empty());
+        assert_eq!(Rect::from_size(5usize, 3).area(), 15);
+    }
+
+    #[test]
+    fn test_contains_and_corners() {
+        let r = Rect::inclusive(Point { x: -1, y: -1 }, Point { x: 1, y: 2 });
+        assert!(r.contains(&Point { x: 1, y: 2 }));
+        assert!(r.contains(&Point { x: -1, y: -1 }));
+        assert!(!r.contains(&Point { x: 2, y: 0 }));
+        assert_eq!(r.top_left(), Point { x: -1, y: -1 });
+        assert_eq!(r.top_right(), Point { x: 1, y: -1 });
+        assert_eq!(r.bottom_left(), Point { x: -1, y: 2 });
+        assert_eq!(r.bottom_right(), Point { x: 1, y: 2 });
+    }
+
+    #[test]
+    fn test_bounds() {
+        let (min, max) = (Point { x: 1, y: 2 }, Point { x: 4, y: 4 });
+        let half_open = Rect::with_bounds(min, max, Bounds::HalfOpen);
+        let inclusive = Rect::with_bounds(min, max, Bounds::Inclusive);
+        assert_eq!(half_open, Rect::new(min, max));
+        assert_eq!(inclusive, Rect::inclusive(min, max));
+        assert!(!half_open.contains(&max) && inclusive.contains(&max));
+        for bounds in [Bounds::HalfOpen, Bounds::Inclusive] {
+            let r = Rect::with_bounds(min, max, bounds);
+            assert_eq!(r.max_corner(bounds), max);
+        }
+        assert_eq!(inclusive.max_corner(Bounds::HalfOpen), Point { x: 5, y: 5 });
+    }
+
+    #[test]
+    fn test_set_operations() {
+        let a = Rect::new(Point { x: 0, y: 0 }, Point { x: 4, y: 4 });
+        let b = Rect::new(Point { x: 2, y: 3 }, Point { x: 6, y: 8 });
+        assert_eq!(
+            a.intersection(&b),
+            Some(Rect::new(Point { x: 2, y: 3 }, Point { x: 4, y: 4 }))
+        );
+        assert_eq!(
+            a.union(&b),
+            Rect::new(Point { x: 0, y: 0 }, Point { x: 6, y: 8 })
+        );
+        let empty = Rect::from_size(0, 0);
+        let d = Rect::inclusive(Point { x: 5, y: 5 }, Point { x: 6, y: 6 });
+        assert_eq!(empty.union(&d), d);
+        assert_eq!(d.union(&empty), d);
+        let c = Rect::new(Point { x: 4, y: 0 }, Point { x: 5, y: 1 });
+        assert_eq!(a.intersection(&c), None);
+        assert_eq!(
+            c.expand(1),
+            Rect::new(Point { x: 3, y: -1 }, Point { x: 6, y: 2 })
+        );
+    }
+
+    #[test]
+    fn test_bounding_and_points() {
+        let pts = vec![
+            Point { x: 2, y: 5 },
+            Point { x: 0, y: 6 },
+            Point { x: 1, y: 4 },
+        ];
+        let r = Rect::bounding(pts).unwrap();
+        assert_eq!(
+            r,
+            Rect::inclusive(Point { x: 0, y: 4 }, Point { x: 2, y: 6 })
+        );
+        assert_eq!(Rect::<i32>::bounding(vec![]), None);
+
+        let r = Rect::from_size(2usize, 2);
+        assert_eq!(
+            r.points().collect::<Vec<_>>(),
+            vec![
+                Point { x: 0, y: 0 },
+                Point { x: 1, y: 0 },
+                Point { x: 0, y: 1 },
+                Point { x: 1, y: 1 },
+            ]
+        );
+        assert_eq!(Rect::from_size(0usize, 3).points().count(), 0);
+    }
+}
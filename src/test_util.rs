@@ -0,0 +1,12 @@
+// Deterministic pseudo-random numbers for tests that check against brute force.
+// A 64 bit linear congruential generator, keeping only the high bits as the
+// low ones repeat quickly. Each call gives a value in 0..n.
+pub fn lcg(seed: u64) -> impl FnMut(i64) -> i64 {
+    let mut state = seed;
+    move |n| {
+        state = state
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        (state >> 33) as i64 % n
+    }
+}
@@ -0,0 +1,136 @@
+use crate::divisors::{gcd, lcm};
+use crate::point::Point;
+
+// A width x height grid whose edges join up, so anything leaving one side comes
+// back on the other, eg. robots teleporting round a room.
+#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq)]
+pub struct Torus {
+    pub width: i64,
+    pub height: i64,
+}
+
+impl Torus {
+    pub fn new(width: i64, height: i64) -> Torus {
+        assert!(width > 0 && height > 0, "torus must have a positive size");
+        Torus { width, height }
+    }
+
+    pub fn size(&self) -> Point<i64> {
+        Point {
+            x: self.width,
+            y: self.height,
+        }
+    }
+
+    // The same place with 0 <= x < width and 0 <= y < height
+    pub fn wrap(&self, p: Point<i64>) -> Point<i64> {
+        p.rem_euclid(&self.size())
+    }
+
+    // Where p gets to after t steps of velocity. Works in i128, so t can be
+    // as large as you like without overflowing.
+    pub fn advance(&self, p: Point<i64>, velocity: Point<i64>, t: i64) -> Point<i64> {
+        let along = |start: i64, v: i64, m: i64| {
+            (start as i128 + v as i128 * t as i128).rem_euclid(m as i128) as i64
+        };
+        Point {
+            x: along(p.x, velocity.x, self.width),
+            y: along(p.y, velocity.y, self.height),
+        }
+    }
+
+    // Steps before anything moving at velocity is back where it started
+    pub fn period(&self, velocity: Point<i64>) -> i64 {
+        let v = self.wrap(velocity);
+        let along = |v: i64, m: i64| m / gcd(m, v);
+        lcm(along(v.x, self.width), along(v.y, self.height))
+    }
+
+    // Which quarter p is in, numbered across then down from 0 at top left.
+    // None on the middle row or column, which only exist for odd sizes.
+    pub fn quadrant(&self, p: Point<i64>) -> Option<usize> {
+        let p = self.wrap(p);
+        let half = |v: i64, m: i64| {
+            if m % 2 == 1 && v == m / 2 {
+                None
+            } else {
+                Some(usize::from(v >= (m + 1) / 2))
+            }
+        };
+        Some(half(p.x, self.width)? + 2 * half(p.y, self.height)?)
+    }
+
+    // How many of the points are in each quadrant, skipping the middle lines
+    pub fn quadrant_counts<I: IntoIterator<Item = Point<i64>>>(&self, points: I) -> [usize; 4] {
+        let mut counts = [0; 4];
+        for q in points.into_iter().filter_map(|p| self.quadrant(p)) {
+            counts[q] += 1;
+        }
+        counts
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::point::Point;
+    use crate::torus::Torus;
+
+    const ROBOTS: &str = "p=0,4 v=3,-3
+p=6,3 v=-1,-3
+p=10,3 v=-1,2
+p=2,0 v=2,-1
+p=0,0 v=1,3
+p=3,0 v=-2,-2
+p=7,6 v=-1,-3
+p=3,0 v=-1,-2
+p=9,3 v=2,3
+p=7,3 v=-1,2
+p=2,4 v=2,-3
+p=9,5 v=-3,-3";
+
+    fn parse() -> Vec<(Point<i64>, Point<i64>)> {
+        ROBOTS
+            .lines()
+            .map(|line| {
+                let v: Vec<i64> = line
+                    .split(['=', ',', ' '])
+                    .filter_map(|s| s.parse().ok())
+                    .collect();
+                (Point { x: v[0], y: v[1] }, Point { x: v[2], y: v[3] })
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_wrap_and_advance() {
+        let room = Torus::new(11, 7);
+        assert_eq!(room.wrap(Point { x: -1, y: 7 }), Point { x: 10, y: 0 });
+        let p = Point { x: 2, y: 4 };
+        let v = Point { x: 2, y: -3 };
+        let stepped = (0..5).fold(p, |q, _| room.wrap(q + v));
+        assert_eq!(room.advance(p, v, 5), stepped);
+        assert_eq!(room.advance(p, v, 5), Point { x: 1, y: 3 });
+        assert_eq!(room.advance(p, v, -5), room.wrap(p - v * 5));
+        // Huge t doesn't overflow, and wraps back round after the period
+        let period = room.period(v);
+        assert_eq!(period, 77);
+        assert_eq!(room.advance(p, v, period * 1_000_000_000_000), p);
+    }
+
+    #[test]
+    fn test_quadrants() {
+        let room = Torus::new(11, 7);
+        let robots = parse();
+        let counts = room.quadrant_counts(robots.iter().map(|&(p, v)| room.advance(p, v, 100)));
+        assert_eq!(counts, [1, 3, 4, 1]);
+        assert_eq!(counts.iter().product::<usize>(), 12);
+
+        assert_eq!(room.quadrant(Point { x: 5, y: 0 }), None);
+        assert_eq!(room.quadrant(Point { x: 0, y: 3 }), None);
+        assert_eq!(room.quadrant(Point { x: 6, y: 2 }), Some(1));
+        // Even sizes split exactly in half
+        let even = Torus::new(4, 4);
+        assert_eq!(even.quadrant(Point { x: 1, y: 2 }), Some(2));
+        assert_eq!(even.quadrant(Point { x: 2, y: 1 }), Some(1));
+    }
+}